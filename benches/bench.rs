@@ -18,7 +18,7 @@ extern crate genmesh;
 extern crate test;
 
 use genmesh::*;
-use genmesh::generators::{Plane, SphereUV};
+use genmesh::generators::{Plane, SphereUV, TorusKnot};
 use genmesh::generators::{SharedVertex, IndexedPolygon};
 use test::{Bencher, black_box};
 
@@ -158,3 +158,34 @@ fn sphere_256x256_index_triangulate(bench: &mut Bencher) {
         }
     });
 }
+
+#[bench]
+fn torus_knot_256x16_index(bench: &mut Bencher) {
+    bench.iter(|| {
+        let knot = TorusKnot::new(2, 3, 0.1, 256, 16);
+        for i in knot.indexed_polygon_iter() {
+            black_box(i);
+        }
+    });
+}
+
+#[bench]
+fn torus_knot_256x16_vertex(bench: &mut Bencher) {
+    bench.iter(|| {
+        let knot = TorusKnot::new(2, 3, 0.1, 256, 16);
+        for i in knot.shared_vertex_iter() {
+            black_box(i);
+        }
+    });
+}
+
+#[bench]
+fn torus_knot_256x16_index_triangulate(bench: &mut Bencher) {
+    bench.iter(|| {
+        let knot = TorusKnot::new(2, 3, 0.1, 256, 16);
+        for i in knot.indexed_polygon_iter()
+                     .triangulate() {
+            black_box(i);
+        }
+    });
+}
@@ -0,0 +1,106 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::Quad;
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a 2D ring with origin of (0, 0)
+#[derive(Clone, Copy)]
+pub struct Annulus {
+    inner_radius: f32,
+    outer_radius: f32,
+    sub_u: usize,
+    rings: usize,
+    u: usize,
+    r: usize
+}
+
+impl Annulus {
+    /// Create a new annulus.
+    /// `inner_radius` and `outer_radius` are the radii of the hole and of the disk.
+    /// `u` is the number of points around the circumference.
+    /// `rings` is the number of subdivisions between the inner and outer edge.
+    pub fn new(inner_radius: f32, outer_radius: f32, u: usize, rings: usize) -> Annulus {
+        assert!(u > 2 && rings > 0);
+        Annulus {
+            inner_radius: inner_radius,
+            outer_radius: outer_radius,
+            sub_u: u,
+            rings: rings,
+            u: 0,
+            r: 0
+        }
+    }
+
+    fn vert(&self, u: usize, r: usize) -> (f32, f32) {
+        let a = (u as f32 / self.sub_u as f32) * PI_2;
+        let t = r as f32 / self.rings as f32;
+        let r = self.inner_radius + (self.outer_radius - self.inner_radius) * t;
+        (r * a.cos(), r * a.sin())
+    }
+
+    fn index(&self, u: usize, r: usize) -> usize {
+        r * self.sub_u + (u % self.sub_u)
+    }
+}
+
+impl Iterator for Annulus {
+    type Item = Quad<(f32, f32)>;
+
+    fn next(&mut self) -> Option<Quad<(f32, f32)>> {
+        if self.u == self.sub_u {
+            self.u = 0;
+            self.r += 1;
+        }
+        if self.r == self.rings {
+            return None;
+        }
+
+        let x = self.vert(self.u,   self.r);
+        let y = self.vert(self.u,   self.r+1);
+        let z = self.vert(self.u+1, self.r+1);
+        let w = self.vert(self.u+1, self.r);
+        self.u += 1;
+
+        Some(Quad::new(x, y, z, w))
+    }
+}
+
+impl SharedVertex<(f32, f32)> for Annulus {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32) {
+        self.vert(idx % self.sub_u, idx / self.sub_u)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        (self.rings + 1) * self.sub_u
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for Annulus {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let u = idx % self.sub_u;
+        let r = idx / self.sub_u;
+
+        Quad::new(self.index(u,   r),
+                  self.index(u,   r+1),
+                  self.index(u+1, r+1),
+                  self.index(u+1, r))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.rings * self.sub_u
+    }
+}
@@ -0,0 +1,146 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{MapVertex, Quad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use math::{Vec3, add, scale, cross, length, normalize};
+
+/// the cubic Bernstein polynomials at `t`
+fn bernstein(t: f32) -> [f32; 4] {
+    let s = 1. - t;
+    [s * s * s, 3. * t * s * s, 3. * t * t * s, t * t * t]
+}
+
+/// the derivatives of the cubic Bernstein polynomials at `t`
+fn bernstein_derivative(t: f32) -> [f32; 4] {
+    let s = 1. - t;
+    [-3. * s * s, 3. * s * s - 6. * t * s, 6. * t * s - 3. * t * t, 3. * t * t]
+}
+
+fn evaluate(patch: &[Vec3; 16], bu: [f32; 4], bv: [f32; 4]) -> Vec3 {
+    let mut p = (0., 0., 0.);
+    for j in 0..4 {
+        for i in 0..4 {
+            p = add(p, scale(patch[4 * j + i], bu[i] * bv[j]));
+        }
+    }
+    p
+}
+
+/// Tessellates one or more bicubic Bezier patches into a grid of quads.
+///
+/// Each patch is made of 16 control points, control point `4 * j + i`
+/// is the `i`th point along u of the `j`th row along v. The quads are wound
+/// counter-clockwise when viewed from the side that the cross product of
+/// the u and v directions points to. Vertices are shared within a patch,
+/// but not between patches.
+#[derive(Clone)]
+pub struct BezierPatch {
+    range: Range<usize>,
+    patches: Vec<[Vec3; 16]>,
+    sub_u: usize,
+    sub_v: usize
+}
+
+impl BezierPatch {
+    /// Create a new set of patches.
+    /// `u` is the number of quads along u of each patch.
+    /// `v` is the number of quads along v of each patch.
+    pub fn new(patches: Vec<[Vec3; 16]>, u: usize, v: usize) -> BezierPatch {
+        assert!(u > 0 && v > 0);
+        BezierPatch {
+            range: 0..patches.len() * u * v,
+            patches: patches,
+            sub_u: u,
+            sub_v: v
+        }
+    }
+
+    fn patch_vertex_count(&self) -> usize {
+        (self.sub_u + 1) * (self.sub_v + 1)
+    }
+
+    /// find the patch and the (u, v) parameters of a shared vertex
+    fn parameters(&self, idx: usize) -> (usize, f32, f32) {
+        let patch = idx / self.patch_vertex_count();
+        let idx = idx % self.patch_vertex_count();
+        let u = (idx % (self.sub_u + 1)) as f32 / self.sub_u as f32;
+        let v = (idx / (self.sub_u + 1)) as f32 / self.sub_v as f32;
+        (patch, u, v)
+    }
+
+    fn normal(&self, patch: usize, u: f32, v: f32) -> Vec3 {
+        let patch = &self.patches[patch];
+        let du = evaluate(patch, bernstein_derivative(u), bernstein(v));
+        let dv = evaluate(patch, bernstein(u), bernstein_derivative(v));
+        cross(du, dv)
+    }
+
+    /// Find the normal of the shared vertex `idx` from the partial
+    /// derivatives of its patch. Where the patch is degenerate, such as
+    /// a row of control points collapsed to a pole, the normal is taken
+    /// from just inside the patch.
+    pub fn shared_normal(&self, idx: usize) -> Vec3 {
+        let (patch, u, v) = self.parameters(idx);
+        let n = self.normal(patch, u, v);
+        if length(n) > 1e-5 {
+            return normalize(n);
+        }
+
+        let (u, v) = (u + (0.5 - u) * 1e-3, v + (0.5 - v) * 1e-3);
+        normalize(self.normal(patch, u, v))
+    }
+}
+
+impl Iterator for BezierPatch {
+    type Item = Quad<Vec3>;
+
+    fn next(&mut self) -> Option<Quad<Vec3>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for BezierPatch {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        let (patch, u, v) = self.parameters(idx);
+        evaluate(&self.patches[patch], bernstein(u), bernstein(v))
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.patches.len() * self.patch_vertex_count()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for BezierPatch {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let patch = idx / (self.sub_u * self.sub_v);
+        let idx = idx % (self.sub_u * self.sub_v);
+        let (u, v) = (idx % self.sub_u, idx / self.sub_u);
+
+        let base = patch * self.patch_vertex_count();
+        let index = |u: usize, v: usize| base + v * (self.sub_u + 1) + u;
+
+        Quad::new(index(u,   v),
+                  index(u+1, v),
+                  index(u+1, v+1),
+                  index(u,   v+1))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.patches.len() * self.sub_u * self.sub_v
+    }
+}
@@ -0,0 +1,159 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::f32::consts::{PI, PI_2};
+use std::num::Float;
+use super::{Quad, Triangle, Polygon};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a capsule centered at (0, 0, 0), made of a cylindrical
+/// body along the z axis with a hemisphere on each end.
+#[derive(Clone, Copy)]
+pub struct Capsule {
+    radius: f32,
+    height: f32,
+    u: usize,
+    v: usize,
+    sub_u: usize,
+    rings: usize
+}
+
+impl Capsule {
+    /// Create a new capsule.
+    /// `radius` is the radius of the body and of the hemispheres.
+    /// `height` is the length of the cylindrical body.
+    /// `u` is the number of points around the body of the capsule.
+    /// `rings` is the number of rings from each pole to the body.
+    pub fn new(radius: f32, height: f32, u: usize, rings: usize) -> Capsule {
+        assert!(u > 1 && rings > 0);
+        Capsule {
+            radius: radius,
+            height: height,
+            u: 0,
+            v: 0,
+            sub_u: u,
+            rings: rings
+        }
+    }
+
+    /// number of points from pole to pole, the seam rings where the
+    /// hemispheres meet the body are shared by the body
+    fn sub_v(&self) -> usize {
+        self.rings * 2 + 1
+    }
+
+    fn vert(&self, u: usize, v: usize) -> (f32, f32, f32) {
+        let u = (u as f32 / self.sub_u as f32) * PI_2;
+        let (v, offset) = if v <= self.rings {
+            ((v as f32 / self.rings as f32) * PI * 0.5,
+             self.height * 0.5)
+        } else {
+            let v = v - 1;
+            ((v as f32 / self.rings as f32) * PI * 0.5,
+             -self.height * 0.5)
+        };
+
+        (self.radius * u.cos() * v.sin(),
+         self.radius * u.sin() * v.sin(),
+         self.radius * v.cos() + offset)
+    }
+}
+
+impl Iterator for Capsule {
+    type Item = Polygon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Polygon<(f32, f32, f32)>> {
+        if self.u == self.sub_u {
+            self.u = 0;
+            self.v += 1;
+            if self.v == self.sub_v() {
+                return None;
+            }
+        }
+
+        let x = self.vert(self.u,   self.v);
+        let y = self.vert(self.u,   self.v+1);
+        let z = self.vert(self.u+1, self.v+1);
+        let w = self.vert(self.u+1, self.v);
+        let v = self.v;
+        self.u += 1;
+
+        if v == 0 {
+            Some(PolyTri(Triangle::new(x, y, z)))
+        } else if v == self.sub_v() - 1 {
+            Some(PolyTri(Triangle::new(z, w, x)))
+        } else {
+            Some(PolyQuad(Quad::new(x, y, z, w)))
+        }
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Capsule {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        if idx == 0 {
+            self.vert(0, 0)
+        } else if idx == self.shared_vertex_count() - 1 {
+            self.vert(0, self.sub_v())
+        } else {
+            // since the bottom verts all map to the same
+            // we jump over them in index space
+            let idx = idx - 1;
+            let u = idx % (self.sub_u);
+            let v = idx / (self.sub_u);
+            self.vert(u, v+1)
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        (self.sub_v() - 1) * (self.sub_u) + 2
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Capsule {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let sub_v = self.sub_v();
+        let u = idx % self.sub_u;
+        let v = idx / self.sub_u;
+
+        let f = |u: usize, v: usize| {
+            if v == 0 {
+                0
+            } else if sub_v == v {
+                (sub_v-1) * (self.sub_u) + 1
+            } else {
+                (v-1) * self.sub_u + (u % self.sub_u) + 1
+            }
+        };
+
+        if v == 0 {
+            PolyTri(Triangle::new(f(u,   v),
+                                  f(u,   v+1),
+                                  f(u+1, v+1)))
+        } else if sub_v - 1 == v {
+            PolyTri(Triangle::new(f(u+1, v+1),
+                                  f(u+1, v),
+                                  f(u,   v)))
+        } else {
+            PolyQuad(Quad::new(f(u,   v),
+                               f(u,   v+1),
+                               f(u+1, v+1),
+                               f(u+1, v)))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_v() * self.sub_u
+    }
+}
@@ -0,0 +1,97 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{MapVertex, Triangle};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a 2D disk with origin of (0, 0) and a radius of 1
+#[derive(Clone)]
+pub struct Circle {
+    range: Range<usize>,
+    sub_u: usize,
+    center: bool
+}
+
+impl Circle {
+    /// Create a new circle, built as a fan of triangles around a center vertex.
+    /// `u` is the number of points around the circumference.
+    pub fn new(u: usize) -> Circle {
+        assert!(u > 2);
+        Circle {
+            range: 0..u,
+            sub_u: u,
+            center: true
+        }
+    }
+
+    /// Create a new circle without a center vertex, this is a regular
+    /// N-gon built as a fan of triangles around its first point.
+    /// `u` is the number of points around the circumference.
+    pub fn ngon(u: usize) -> Circle {
+        assert!(u > 2);
+        Circle {
+            range: 0..u-2,
+            sub_u: u,
+            center: false
+        }
+    }
+
+    fn vert(&self, u: usize) -> (f32, f32) {
+        let a = (u as f32 / self.sub_u as f32) * PI_2;
+        (a.cos(), a.sin())
+    }
+}
+
+impl Iterator for Circle {
+    type Item = Triangle<(f32, f32)>;
+
+    fn next(&mut self) -> Option<Triangle<(f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32)> for Circle {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32) {
+        if !self.center {
+            self.vert(idx)
+        } else if idx == 0 {
+            (0., 0.)
+        } else {
+            self.vert(idx - 1)
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        if self.center { self.sub_u + 1 } else { self.sub_u }
+    }
+}
+
+impl IndexedPolygon<Triangle<usize>> for Circle {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        if self.center {
+            Triangle::new(0, idx + 1, (idx + 1) % self.sub_u + 1)
+        } else {
+            Triangle::new(0, idx + 1, idx + 2)
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        if self.center { self.sub_u } else { self.sub_u - 2 }
+    }
+}
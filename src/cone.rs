@@ -0,0 +1,205 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{Quad, Triangle, Polygon, MapVertex};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a cone or a truncated cone (frustum) with a height of 2,
+/// centered at (0, 0, 0). The axis of the cone is the z axis, the bottom
+/// is at z = -1 and the top at z = 1.
+#[derive(Clone)]
+pub struct Cone {
+    range: Range<usize>,
+    bottom_radius: f32,
+    top_radius: f32,
+    sub_u: usize,
+    sub_h: usize,
+    top: bool,
+    bottom: bool
+}
+
+impl Cone {
+    /// Create a new cone with a bottom radius of 1 and the apex at z = 1.
+    /// The bottom is capped.
+    /// `u` is the number of points around the circumference.
+    pub fn new(u: usize) -> Cone {
+        Cone::frustum(1., 0., u, 1)
+    }
+
+    /// Create a new truncated cone with both ends capped. A radius of
+    /// 0 collapses that end of the cone to a single apex vertex.
+    /// `u` is the number of points around the circumference.
+    /// `h` is the number of segments along the height.
+    pub fn frustum(bottom_radius: f32, top_radius: f32, u: usize, h: usize) -> Cone {
+        assert!(u > 1 && h > 0);
+        assert!(bottom_radius > 0. || top_radius > 0.);
+        let mut cone = Cone {
+            range: 0..0,
+            bottom_radius: bottom_radius,
+            top_radius: top_radius,
+            sub_u: u,
+            sub_h: h,
+            top: true,
+            bottom: true
+        };
+        cone.range = 0..cone.indexed_polygon_count();
+        cone
+    }
+
+    /// Turn the `top` (z = 1) and `bottom` (z = -1) caps on or off.
+    /// Each cap is a fan of triangles around a center vertex, an
+    /// end with a radius of 0 is never capped.
+    pub fn caps(mut self, top: bool, bottom: bool) -> Cone {
+        self.top = top;
+        self.bottom = bottom;
+        self.range = 0..self.indexed_polygon_count();
+        self
+    }
+
+    fn radius(&self, h: usize) -> f32 {
+        let t = h as f32 / self.sub_h as f32;
+        self.bottom_radius + (self.top_radius - self.bottom_radius) * t
+    }
+
+    fn vert(&self, u: usize, h: usize) -> (f32, f32, f32) {
+        let a = (u as f32 / self.sub_u as f32) * PI_2;
+        let r = self.radius(h);
+        let z = (2. / self.sub_h as f32) * h as f32 - 1.;
+        (r * a.cos(), r * a.sin(), z)
+    }
+
+    fn bottom_apex(&self) -> bool { self.bottom_radius == 0. }
+    fn top_apex(&self) -> bool { self.top_radius == 0. }
+    fn bottom_cap(&self) -> bool { self.bottom && !self.bottom_apex() }
+    fn top_cap(&self) -> bool { self.top && !self.top_apex() }
+
+    fn ring_count(&self) -> usize {
+        let mut count = (self.sub_h + 1) * self.sub_u;
+        if self.bottom_apex() { count -= self.sub_u - 1; }
+        if self.top_apex() { count -= self.sub_u - 1; }
+        count
+    }
+
+    fn ring_index(&self, u: usize, h: usize) -> usize {
+        if self.bottom_apex() {
+            if h == 0 {
+                0
+            } else {
+                (h - 1) * self.sub_u + (u % self.sub_u) + 1
+            }
+        } else if self.top_apex() && h == self.sub_h {
+            h * self.sub_u
+        } else {
+            h * self.sub_u + (u % self.sub_u)
+        }
+    }
+
+    fn bottom_index(&self) -> usize {
+        self.ring_count()
+    }
+
+    fn top_index(&self) -> usize {
+        self.ring_count() + if self.bottom_cap() { 1 } else { 0 }
+    }
+}
+
+impl Iterator for Cone {
+    type Item = Polygon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Polygon<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Cone {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        if idx < self.ring_count() {
+            if !self.bottom_apex() {
+                self.vert(idx % self.sub_u, idx / self.sub_u)
+            } else if idx == 0 {
+                self.vert(0, 0)
+            } else {
+                // since the bottom verts all map to the same
+                // we jump over them in index space
+                let idx = idx - 1;
+                self.vert(idx % self.sub_u, idx / self.sub_u + 1)
+            }
+        } else if self.bottom_cap() && idx == self.bottom_index() {
+            (0., 0., -1.)
+        } else if self.top_cap() && idx == self.top_index() {
+            (0., 0., 1.)
+        } else {
+            panic!("{} vertex is higher then {}", idx, self.shared_vertex_count())
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.ring_count() +
+            if self.bottom_cap() { 1 } else { 0 } +
+            if self.top_cap() { 1 } else { 0 }
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Cone {
+    fn indexed_polygon(&self, i: usize) -> Polygon<usize> {
+        let mut idx = i;
+
+        if self.bottom_cap() {
+            if idx < self.sub_u {
+                return PolyTri(Triangle::new(self.bottom_index(),
+                                             self.ring_index(idx+1, 0),
+                                             self.ring_index(idx,   0)));
+            }
+            idx -= self.sub_u;
+        }
+
+        if idx < self.sub_u * self.sub_h {
+            let u = idx % self.sub_u;
+            let h = idx / self.sub_u;
+            let x = self.ring_index(u,   h+1);
+            let y = self.ring_index(u,   h);
+            let z = self.ring_index(u+1, h);
+            let w = self.ring_index(u+1, h+1);
+
+            return if h == 0 && self.bottom_apex() {
+                PolyTri(Triangle::new(x, y, w))
+            } else if h == self.sub_h - 1 && self.top_apex() {
+                PolyTri(Triangle::new(x, y, z))
+            } else {
+                PolyQuad(Quad::new(x, y, z, w))
+            };
+        }
+        idx -= self.sub_u * self.sub_h;
+
+        if self.top_cap() && idx < self.sub_u {
+            return PolyTri(Triangle::new(self.top_index(),
+                                         self.ring_index(idx,   self.sub_h),
+                                         self.ring_index(idx+1, self.sub_h)));
+        }
+
+        panic!("{} polygon is higher then {}", i, self.indexed_polygon_count())
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_u * self.sub_h +
+            if self.bottom_cap() { self.sub_u } else { 0 } +
+            if self.top_cap() { self.sub_u } else { 0 }
+    }
+}
@@ -0,0 +1,181 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+//! a grid of samples of a field, shared by the meshers that place one
+//! vertex in each cell that the surface passes through
+
+use std::collections::HashMap;
+use std::num::Float;
+use super::Quad;
+use math::{Vec3, add, sub, scale};
+
+pub type Point = (usize, usize, usize);
+
+pub struct Grid {
+    min: Vec3,
+    step: Vec3,
+    resolution: Point,
+    samples: Vec<f32>,
+    iso: f32
+}
+
+impl Grid {
+    /// sample `field` at every point of a grid of `resolution` cells
+    /// between `min` and `max`
+    pub fn new<F>(field: &F, min: Vec3, max: Vec3, resolution: Point, iso: f32) -> Grid
+        where F: Fn(f32, f32, f32) -> f32 {
+
+        let (nx, ny, nz) = resolution;
+        assert!(nx > 0 && ny > 0 && nz > 0);
+
+        let mut grid = Grid {
+            min: min,
+            step: ((max.0 - min.0) / nx as f32,
+                   (max.1 - min.1) / ny as f32,
+                   (max.2 - min.2) / nz as f32),
+            resolution: resolution,
+            samples: Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1)),
+            iso: iso
+        };
+
+        for k in 0..nz + 1 {
+            for j in 0..ny + 1 {
+                for i in 0..nx + 1 {
+                    let (x, y, z) = grid.point((i, j, k));
+                    grid.samples.push(field(x, y, z));
+                }
+            }
+        }
+        grid
+    }
+
+    /// the position of a point of the grid
+    pub fn point(&self, (i, j, k): Point) -> Vec3 {
+        (self.min.0 + self.step.0 * i as f32,
+         self.min.1 + self.step.1 * j as f32,
+         self.min.2 + self.step.2 * k as f32)
+    }
+
+    /// the smallest distance between two points of the grid
+    pub fn min_step(&self) -> f32 {
+        self.step.0.min(self.step.1).min(self.step.2)
+    }
+
+    fn sample(&self, (i, j, k): Point) -> f32 {
+        let (nx, ny, _) = self.resolution;
+        self.samples[(k * (ny + 1) + j) * (nx + 1) + i]
+    }
+
+    fn inside(&self, p: Point) -> bool {
+        self.sample(p) < self.iso
+    }
+
+    /// the value of the field on the surface
+    pub fn iso(&self) -> f32 {
+        self.iso
+    }
+
+    /// the edges of `cell` that the surface crosses, as the position and
+    /// the sample at each end
+    pub fn crossed_edges(&self, (i, j, k): Point) -> Vec<((Vec3, f32), (Vec3, f32))> {
+        let mut edges = Vec::new();
+        for &(a, b) in [((0, 0, 0), (1, 0, 0)), ((0, 1, 0), (1, 1, 0)),
+                        ((0, 0, 1), (1, 0, 1)), ((0, 1, 1), (1, 1, 1)),
+                        ((0, 0, 0), (0, 1, 0)), ((1, 0, 0), (1, 1, 0)),
+                        ((0, 0, 1), (0, 1, 1)), ((1, 0, 1), (1, 1, 1)),
+                        ((0, 0, 0), (0, 0, 1)), ((1, 0, 0), (1, 0, 1)),
+                        ((0, 1, 0), (0, 1, 1)), ((1, 1, 0), (1, 1, 1))].iter() {
+            let a = (i + a.0, j + a.1, k + a.2);
+            let b = (i + b.0, j + b.1, k + b.2);
+            if self.inside(a) != self.inside(b) {
+                edges.push(((self.point(a), self.sample(a)),
+                            (self.point(b), self.sample(b))));
+            }
+        }
+        edges
+    }
+
+    /// the points where the surface crosses the edges of `cell`, assuming
+    /// that the field is linear along each edge
+    pub fn crossings(&self, cell: Point) -> Vec<Vec3> {
+        self.crossed_edges(cell).iter().map(|&((a, fa), (b, fb))| {
+            let t = (self.iso - fa) / (fb - fa);
+            add(a, scale(sub(b, a), t))
+        }).collect()
+    }
+
+    /// find a quad for every edge of the grid that the surface crosses,
+    /// joining the vertices of the four cells around the edge. `place`
+    /// finds the vertex of a cell, it is only called once for each cell.
+    /// The quads face from the inside of the surface to the outside.
+    pub fn contour<P>(&self, mut place: P) -> (Vec<Vec3>, Vec<Quad<usize>>)
+        where P: FnMut(Point) -> Vec3 {
+
+        let (nx, ny, nz) = self.resolution;
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        let mut cells = HashMap::new();
+
+        // each axis, with the two axes that the cells around an edge along
+        // it are spread over, in counter-clockwise order
+        for &(axis, a, b) in [(0, 1, 2), (1, 2, 0), (2, 0, 1)].iter() {
+            for k in 0..nz + 1 {
+                for j in 0..ny + 1 {
+                    for i in 0..nx + 1 {
+                        let p = [i, j, k];
+                        let size = [nx, ny, nz];
+
+                        // only edges with a cell on every side
+                        if p[axis] == size[axis] || p[a] == 0 || p[b] == 0 ||
+                           p[a] == size[a] || p[b] == size[b] {
+                            continue;
+                        }
+
+                        let mut q = p;
+                        q[axis] += 1;
+                        let (start, end) = ((p[0], p[1], p[2]), (q[0], q[1], q[2]));
+                        if self.inside(start) == self.inside(end) {
+                            continue;
+                        }
+
+                        let mut quad = [0; 4];
+                        for (n, &(da, db)) in [(1, 1), (0, 1), (0, 0), (1, 0)].iter().enumerate() {
+                            let mut c = p;
+                            c[a] -= da;
+                            c[b] -= db;
+                            let cell = (c[0], c[1], c[2]);
+                            quad[n] = match cells.get(&cell) {
+                                Some(&idx) => idx,
+                                None => {
+                                    let idx = vertices.len();
+                                    vertices.push(place(cell));
+                                    cells.insert(cell, idx);
+                                    idx
+                                }
+                            };
+                        }
+
+                        faces.push(if self.inside(start) {
+                            Quad::new(quad[0], quad[1], quad[2], quad[3])
+                        } else {
+                            Quad::new(quad[3], quad[2], quad[1], quad[0])
+                        });
+                    }
+                }
+            }
+        }
+
+        (vertices, faces)
+    }
+}
@@ -15,40 +15,222 @@
 use std::ops::Range;
 use super::{MapVertex, Quad};
 use super::generators::{SharedVertex, IndexedPolygon};
+use normals::Normals;
+use textured::{Textured, TexturedVertex, from_parts};
+use math::{Vec3, cross};
+
+/// each face is described by the lattice corner it starts at and the two
+/// directions that its grid runs along, the faces are wound counter-clockwise
+const FACES: [([usize; 3], [isize; 3], [isize; 3]); 6] = [
+    ([0, 0, 0], [ 0,  1,  0], [0, 0, 1]),
+    ([1, 1, 0], [ 0, -1,  0], [0, 0, 1]),
+    ([1, 0, 0], [-1,  0,  0], [0, 0, 1]),
+    ([0, 1, 1], [ 0,  0, -1], [1, 0, 0]),
+    ([0, 0, 0], [ 1,  0,  0], [0, 1, 0]),
+    ([1, 0, 1], [-1,  0,  0], [0, 1, 0])
+];
+
+fn axis(dir: [isize; 3]) -> usize {
+    if dir[0] != 0 { 0 } else if dir[1] != 0 { 1 } else { 2 }
+}
+
+fn direction(dir: [isize; 3]) -> Vec3 {
+    (dir[0] as f32, dir[1] as f32, dir[2] as f32)
+}
 
 /// A perfect cube, centered at (0, 0, 0) with each face starting at 1/-1 away from the origin
 #[derive(Clone)]
 pub struct Cube {
-    range: Range<usize>
+    range: Range<usize>,
+    subdivide_x: usize,
+    subdivide_y: usize,
+    subdivide_z: usize
 }
 
 impl Cube {
     /// create a new cube generator
     pub fn new() -> Cube {
-        Cube { range: 0..6 }
+        Cube::subdivide(1, 1, 1)
     }
 
-    fn vert(&self, idx: usize) -> (f32, f32, f32) {
-        let x = if idx & 4 == 4 { 1.} else { -1. };
-        let y = if idx & 2 == 2 { 1.} else { -1. };
-        let z = if idx & 1 == 1 { 1.} else { -1. };
+    /// create a subdivided cube. Each face is a grid of quads, vertices
+    /// along the edges are shared between the neighbouring faces.
+    /// x is the number of subdivisions in the x axis
+    /// y is the number of subdivisions in the y axis
+    /// z is the number of subdivisions in the z axis
+    pub fn subdivide(x: usize, y: usize, z: usize) -> Cube {
+        assert!(x > 0 && y > 0 && z > 0);
+        let mut cube = Cube {
+            range: 0..0,
+            subdivide_x: x,
+            subdivide_y: y,
+            subdivide_z: z
+        };
+        cube.range = 0..cube.indexed_polygon_count();
+        cube
+    }
+
+    /// Create a generator with flat normals, each face gets its own
+    /// vertices so the corners of the cube are not shared.
+    pub fn normals(&self) -> Normals<Quad<usize>> {
+        Normals::flat(self)
+    }
+
+    /// Create a generator with texture coordinates, each face gets its own
+    /// vertices and is covered by the texture from (0, 0) to (1, 1). The
+    /// normals are flat.
+    pub fn textured(&self) -> Textured<Quad<usize>> {
+        let mut vertices = Vec::new();
+        let mut polygons = Vec::with_capacity(self.indexed_polygon_count());
+
+        for face in 0..6 {
+            let (_, a, b) = FACES[face];
+            let (sa, sb) = self.face_size(face);
+            let normal = cross(direction(b), direction(a));
+            let base = vertices.len();
+
+            for s in 0..sa+1 {
+                for t in 0..sb+1 {
+                    let (i, j, k) = self.face_point(face, s, t);
+                    vertices.push(TexturedVertex {
+                        pos: self.vert(i, j, k),
+                        normal: normal,
+                        uv: (t as f32 / sb as f32, s as f32 / sa as f32)
+                    });
+                }
+            }
+
+            let index = |s: usize, t: usize| base + s * (sb + 1) + t;
+            for s in 0..sa {
+                for t in 0..sb {
+                    polygons.push(Quad::new(index(s,   t),
+                                            index(s,   t+1),
+                                            index(s+1, t+1),
+                                            index(s+1, t)));
+                }
+            }
+        }
+        from_parts(vertices, polygons)
+    }
+
+    fn size(&self) -> [usize; 3] {
+        [self.subdivide_x, self.subdivide_y, self.subdivide_z]
+    }
+
+    /// number of lattice points in a slab of constant x that touches
+    /// the x = 1 or x = -1 face
+    fn full_slab(&self) -> usize {
+        (self.subdivide_y + 1) * (self.subdivide_z + 1)
+    }
+
+    /// number of lattice points in a slab of constant x that only
+    /// contains the edges of the other four faces
+    fn ring_slab(&self) -> usize {
+        2 * (self.subdivide_z + 1) + 2 * (self.subdivide_y - 1)
+    }
+
+    /// find the index of the lattice point (i, j, k) on the surface of the
+    /// cube, the points are ordered by i, then j, then k.
+    fn lattice_index(&self, i: usize, j: usize, k: usize) -> usize {
+        let (y, z) = (self.subdivide_y, self.subdivide_z);
+
+        if i == 0 {
+            j * (z + 1) + k
+        } else if i == self.subdivide_x {
+            self.full_slab() + (i - 1) * self.ring_slab() + j * (z + 1) + k
+        } else {
+            let base = self.full_slab() + (i - 1) * self.ring_slab();
+            if j == 0 {
+                base + k
+            } else if j == y {
+                base + (z + 1) + 2 * (y - 1) + k
+            } else {
+                base + (z + 1) + 2 * (j - 1) + if k == 0 { 0 } else { 1 }
+            }
+        }
+    }
+
+    /// Find the lattice coordinates of the shared vertex `idx`, each
+    /// coordinate runs from 0 to the number of subdivisions along its axis.
+    /// This lets other generators reuse the cube's indexing.
+    pub fn lattice(&self, idx: usize) -> (usize, usize, usize) {
+        let (x, y, z) = (self.subdivide_x, self.subdivide_y, self.subdivide_z);
+        let full = |idx: usize| (idx / (z + 1), idx % (z + 1));
+
+        if idx < self.full_slab() {
+            let (j, k) = full(idx);
+            return (0, j, k);
+        }
+
+        let idx = idx - self.full_slab();
+        if idx >= (x - 1) * self.ring_slab() {
+            let (j, k) = full(idx - (x - 1) * self.ring_slab());
+            return (x, j, k);
+        }
+
+        let i = idx / self.ring_slab() + 1;
+        let idx = idx % self.ring_slab();
+        if idx < z + 1 {
+            (i, 0, idx)
+        } else if idx < (z + 1) + 2 * (y - 1) {
+            let idx = idx - (z + 1);
+            (i, idx / 2 + 1, if idx % 2 == 0 { 0 } else { z })
+        } else {
+            (i, y, idx - (z + 1) - 2 * (y - 1))
+        }
+    }
+
+    fn vert(&self, i: usize, j: usize, k: usize) -> (f32, f32, f32) {
+        let x = (2. / self.subdivide_x as f32) * i as f32 - 1.;
+        let y = (2. / self.subdivide_y as f32) * j as f32 - 1.;
+        let z = (2. / self.subdivide_z as f32) * k as f32 - 1.;
         (x, y, z)
     }
 
-    fn face_indexed(&self, idx: usize) -> Quad<usize> {
-        match idx {
-            0 => Quad::new(0b000, 0b001, 0b011, 0b010),
-            1 => Quad::new(0b110, 0b111, 0b101, 0b100),
-            2 => Quad::new(0b100, 0b101, 0b001, 0b000),
-            3 => Quad::new(0b011, 0b111, 0b110, 0b010),
-            4 => Quad::new(0b000, 0b010, 0b110, 0b100),
-            5 => Quad::new(0b101, 0b111, 0b011, 0b001),
-            idx => panic!("{} face is higher then 6", idx)
+    fn face_size(&self, face: usize) -> (usize, usize) {
+        let (_, a, b) = FACES[face];
+        let size = self.size();
+        (size[axis(a)], size[axis(b)])
+    }
+
+    /// find the lattice point that is `s` steps along the first direction
+    /// and `t` steps along the second direction of `face`
+    fn face_point(&self, face: usize, s: usize, t: usize) -> (usize, usize, usize) {
+        let (origin, a, b) = FACES[face];
+        let size = self.size();
+        let mut p = [0; 3];
+        for n in 0..3 {
+            p[n] = ((origin[n] * size[n]) as isize +
+                    s as isize * a[n] + t as isize * b[n]) as usize;
         }
+        (p[0], p[1], p[2])
+    }
+
+    fn face_indexed(&self, i: usize) -> Quad<usize> {
+        let mut idx = i;
+        for face in 0..6 {
+            let (sa, sb) = self.face_size(face);
+            if idx >= sa * sb {
+                idx -= sa * sb;
+                continue;
+            }
+
+            let (s, t) = (idx / sb, idx % sb);
+            let point = |s: usize, t: usize| {
+                let (i, j, k) = self.face_point(face, s, t);
+                self.lattice_index(i, j, k)
+            };
+
+            return Quad::new(point(s,   t),
+                             point(s,   t+1),
+                             point(s+1, t+1),
+                             point(s+1, t));
+        }
+        panic!("{} face is higher then {}", i, self.indexed_polygon_count())
     }
 
     fn face(&self, idx: usize) -> Quad<(f32, f32, f32)> {
-        self.face_indexed(idx).map_vertex(|i| self.vert(i))
+        self.face_indexed(idx).map_vertex(|i| self.shared_vertex(i))
     }
 } 
 
@@ -62,10 +244,13 @@ impl Iterator for Cube {
 
 impl SharedVertex<(f32, f32, f32)> for Cube {
     fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
-        self.vert(idx)
+        let (i, j, k) = self.lattice(idx);
+        self.vert(i, j, k)
     }
 
-    fn shared_vertex_count(&self) -> usize { 8 }
+    fn shared_vertex_count(&self) -> usize {
+        2 * self.full_slab() + (self.subdivide_x - 1) * self.ring_slab()
+    }
 }
 
 impl IndexedPolygon<Quad<usize>> for Cube {
@@ -73,5 +258,41 @@ impl IndexedPolygon<Quad<usize>> for Cube {
         self.face_indexed(idx)
     }
 
-    fn indexed_polygon_count(&self) -> usize { 6 }
-}
\ No newline at end of file
+    fn indexed_polygon_count(&self) -> usize {
+        let (x, y, z) = (self.subdivide_x, self.subdivide_y, self.subdivide_z);
+        2 * (y * z + x * z + x * y)
+    }
+}
+
+#[test]
+fn test_cube_new() {
+    let cube = Cube::new();
+    assert_eq!(cube.shared_vertex_count(), 8);
+    assert_eq!(cube.indexed_polygon_count(), 6);
+
+    for idx in 0..8 {
+        let x = if idx & 4 == 4 { 1.} else { -1. };
+        let y = if idx & 2 == 2 { 1.} else { -1. };
+        let z = if idx & 1 == 1 { 1.} else { -1. };
+        assert_eq!(cube.shared_vertex(idx), (x, y, z));
+    }
+
+    let faces = [Quad::new(0b000, 0b001, 0b011, 0b010),
+                 Quad::new(0b110, 0b111, 0b101, 0b100),
+                 Quad::new(0b100, 0b101, 0b001, 0b000),
+                 Quad::new(0b011, 0b111, 0b110, 0b010),
+                 Quad::new(0b000, 0b010, 0b110, 0b100),
+                 Quad::new(0b101, 0b111, 0b011, 0b001)];
+    for (idx, face) in faces.iter().enumerate() {
+        assert_eq!(&cube.indexed_polygon(idx), face);
+    }
+}
+
+#[test]
+fn test_cube_lattice() {
+    let cube = Cube::subdivide(3, 2, 4);
+    for idx in 0..cube.shared_vertex_count() {
+        let (i, j, k) = cube.lattice(idx);
+        assert_eq!(cube.lattice_index(i, j, k), idx);
+    }
+}
@@ -0,0 +1,144 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::{PI, SQRT_2};
+use std::num::Float;
+use super::{MapVertex, Quad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use cube::Cube;
+use math::{Vec3, normalize};
+
+/// Represents a sphere with radius of 1, centered at (0, 0, 0), built by
+/// projecting a subdivided `Cube` onto the sphere. There are no poles,
+/// each of the six faces of the cube stays a square grid of quads.
+#[derive(Clone)]
+pub struct CubeSphere {
+    range: Range<usize>,
+    cube: Cube,
+    subdivisions: usize,
+    equal_area: bool
+}
+
+/// map the point (`a`, `b`) of the face of the cube at z = 1 onto the
+/// sphere so that every part of the face keeps its share of the area.
+///
+/// The face is split into eight triangles by its axes and diagonals. In the
+/// triangle where 0 <= b <= a, lines through the center of the face become
+/// great circles through the pole and lines of constant a become curves
+/// that enclose an area proportional to a².
+fn equal_area(a: f32, b: f32) -> Vec3 {
+    if a == 0. && b == 0. {
+        return (0., 0., 1.);
+    }
+
+    let (p, q, swap) = if b.abs() > a.abs() {
+        (b.abs(), a.abs(), true)
+    } else {
+        (a.abs(), b.abs(), false)
+    };
+
+    // the azimuth of the great circle that the line b = s * a maps onto
+    let beta = (q / p) * PI / 12.;
+    let alpha = (beta.sin() / (SQRT_2 - beta.cos())).atan();
+    let phi = alpha + beta;
+
+    // how far down the great circle this point is, `1 - cos(theta)`
+    let c = phi.cos();
+    let h = p * p * (1. - c / (1. + c * c).sqrt());
+    let r = (h * (2. - h)).sqrt();
+
+    let (x, y) = (r * phi.cos(), r * phi.sin());
+    let (x, y) = if swap { (y, x) } else { (x, y) };
+    (x * a.signum(), y * b.signum(), 1. - h)
+}
+
+impl CubeSphere {
+    /// Create a new cube sphere, each vertex of the cube is normalized.
+    /// `n` is the number of quads along each edge of a face.
+    pub fn new(n: usize) -> CubeSphere {
+        CubeSphere::build(n, false)
+    }
+
+    /// Create a new cube sphere using an equal-area mapping, every quad
+    /// covers close to the same area of the sphere.
+    /// `n` is the number of quads along each edge of a face.
+    pub fn equal_area(n: usize) -> CubeSphere {
+        CubeSphere::build(n, true)
+    }
+
+    fn build(n: usize, equal_area: bool) -> CubeSphere {
+        let cube = Cube::subdivide(n, n, n);
+        CubeSphere {
+            range: 0..cube.indexed_polygon_count(),
+            cube: cube,
+            subdivisions: n,
+            equal_area: equal_area
+        }
+    }
+
+    /// Find the face of the cube that the quad `idx` belongs to, the faces
+    /// are numbered in the order -x, +x, -y, +y, -z, +z. Each face is made of
+    /// `n * n` consecutive quads.
+    pub fn cube_face(&self, idx: usize) -> usize {
+        assert!(idx < self.indexed_polygon_count());
+        idx / (self.subdivisions * self.subdivisions)
+    }
+}
+
+impl Iterator for CubeSphere {
+    type Item = Quad<Vec3>;
+
+    fn next(&mut self) -> Option<Quad<Vec3>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for CubeSphere {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        let (x, y, z) = self.cube.shared_vertex(idx);
+        if !self.equal_area {
+            return normalize((x, y, z));
+        }
+
+        // rotate the face the vertex is on to z = 1, and back again
+        let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+        if ax >= ay && ax >= az {
+            let (a, b, c) = equal_area(y, z);
+            (c * x.signum(), a, b)
+        } else if ay >= az {
+            let (a, b, c) = equal_area(z, x);
+            (b, c * y.signum(), a)
+        } else {
+            let (a, b, c) = equal_area(x, y);
+            (a, b, c * z.signum())
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.cube.shared_vertex_count()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for CubeSphere {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        self.cube.indexed_polygon(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.cube.indexed_polygon_count()
+    }
+}
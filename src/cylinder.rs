@@ -0,0 +1,155 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{Quad, Triangle, Polygon, MapVertex};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a cylinder with radius of 1, height of 2, and centered at (0, 0, 0)
+/// the axis of the cylinder is the z axis.
+#[derive(Clone)]
+pub struct Cylinder {
+    range: Range<usize>,
+    sub_u: usize,
+    sub_h: usize,
+    top: bool,
+    bottom: bool
+}
+
+impl Cylinder {
+    /// Create a new cylinder with both caps.
+    /// `u` is the number of points around the circumference.
+    pub fn new(u: usize) -> Cylinder {
+        Cylinder::subdivide(u, 1)
+    }
+
+    /// Create a new subdivided cylinder with both caps.
+    /// `u` is the number of points around the circumference.
+    /// `h` is the number of segments along the height.
+    pub fn subdivide(u: usize, h: usize) -> Cylinder {
+        assert!(u > 1 && h > 0);
+        let mut cylinder = Cylinder {
+            range: 0..0,
+            sub_u: u,
+            sub_h: h,
+            top: true,
+            bottom: true
+        };
+        cylinder.range = 0..cylinder.indexed_polygon_count();
+        cylinder
+    }
+
+    /// Turn the `top` (z = 1) and `bottom` (z = -1) caps on or off.
+    /// Each cap is a fan of triangles around a center vertex.
+    pub fn caps(mut self, top: bool, bottom: bool) -> Cylinder {
+        self.top = top;
+        self.bottom = bottom;
+        self.range = 0..self.indexed_polygon_count();
+        self
+    }
+
+    fn vert(&self, u: usize, h: usize) -> (f32, f32, f32) {
+        let a = (u as f32 / self.sub_u as f32) * PI_2;
+        let z = (2. / self.sub_h as f32) * h as f32 - 1.;
+        (a.cos(), a.sin(), z)
+    }
+
+    fn ring_count(&self) -> usize {
+        (self.sub_h + 1) * self.sub_u
+    }
+
+    fn ring_index(&self, u: usize, h: usize) -> usize {
+        h * self.sub_u + (u % self.sub_u)
+    }
+
+    fn bottom_index(&self) -> usize {
+        self.ring_count()
+    }
+
+    fn top_index(&self) -> usize {
+        self.ring_count() + if self.bottom { 1 } else { 0 }
+    }
+}
+
+impl Iterator for Cylinder {
+    type Item = Polygon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Polygon<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Cylinder {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        if idx < self.ring_count() {
+            self.vert(idx % self.sub_u, idx / self.sub_u)
+        } else if self.bottom && idx == self.bottom_index() {
+            (0., 0., -1.)
+        } else if self.top && idx == self.top_index() {
+            (0., 0., 1.)
+        } else {
+            panic!("{} vertex is higher then {}", idx, self.shared_vertex_count())
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.ring_count() +
+            if self.bottom { 1 } else { 0 } +
+            if self.top { 1 } else { 0 }
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Cylinder {
+    fn indexed_polygon(&self, i: usize) -> Polygon<usize> {
+        let mut idx = i;
+
+        if self.bottom {
+            if idx < self.sub_u {
+                return PolyTri(Triangle::new(self.bottom_index(),
+                                             self.ring_index(idx+1, 0),
+                                             self.ring_index(idx,   0)));
+            }
+            idx -= self.sub_u;
+        }
+
+        if idx < self.sub_u * self.sub_h {
+            let u = idx % self.sub_u;
+            let h = idx / self.sub_u;
+            return PolyQuad(Quad::new(self.ring_index(u,   h+1),
+                                      self.ring_index(u,   h),
+                                      self.ring_index(u+1, h),
+                                      self.ring_index(u+1, h+1)));
+        }
+        idx -= self.sub_u * self.sub_h;
+
+        if self.top && idx < self.sub_u {
+            return PolyTri(Triangle::new(self.top_index(),
+                                         self.ring_index(idx,   self.sub_h),
+                                         self.ring_index(idx+1, self.sub_h)));
+        }
+
+        panic!("{} polygon is higher then {}", i, self.indexed_polygon_count())
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_u * self.sub_h +
+            if self.bottom { self.sub_u } else { 0 } +
+            if self.top { self.sub_u } else { 0 }
+    }
+}
@@ -0,0 +1,90 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::num::Float;
+use super::{MapVertex, Pentagon};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// the golden ratio
+const PHI: f32 = 1.618034;
+/// the inverse of the golden ratio
+const INV: f32 = 0.618034;
+
+const VERTICES: [(f32, f32, f32); 20] = [
+    (-1.,   -1.,   -1.), (-1.,   -1.,    1.), (-1.,    1.,   -1.), (-1.,    1.,    1.),
+    ( 1.,   -1.,   -1.), ( 1.,   -1.,    1.), ( 1.,    1.,   -1.), ( 1.,    1.,    1.),
+    ( 0.,   -INV, -PHI), (-INV, -PHI,  0.),   (-PHI,  0.,   -INV), ( 0.,   -INV,  PHI),
+    (-INV,  PHI,  0.),   (-PHI,  0.,    INV), ( 0.,    INV, -PHI), ( INV, -PHI,  0.),
+    ( PHI,  0.,   -INV), ( 0.,    INV,  PHI), ( INV,  PHI,  0.),   ( PHI,  0.,    INV)
+];
+
+const FACES: [[usize; 5]; 12] = [
+    [0,  8,  4, 15,  9], [1,  9, 15,  5, 11], [2, 12, 18,  6, 14],
+    [3, 17,  7, 18, 12], [0,  9,  1, 13, 10], [2, 10, 13,  3, 12],
+    [4, 16, 19,  5, 15], [6, 18,  7, 19, 16], [0, 10,  2, 14,  8],
+    [1, 11, 17,  3, 13], [4,  8, 14,  6, 16], [5, 19,  7, 17, 11]
+];
+
+/// A regular dodecahedron, centered at (0, 0, 0) with each vertex 1 away from the origin
+#[derive(Clone)]
+pub struct Dodecahedron {
+    range: Range<usize>
+}
+
+impl Dodecahedron {
+    /// create a new dodecahedron generator
+    pub fn new() -> Dodecahedron {
+        Dodecahedron { range: 0..12 }
+    }
+
+    fn vert(&self, idx: usize) -> (f32, f32, f32) {
+        let (x, y, z) = VERTICES[idx];
+        let s = 1. / 3f32.sqrt();
+        (x * s, y * s, z * s)
+    }
+
+    fn face_indexed(&self, idx: usize) -> Pentagon<usize> {
+        let f = FACES[idx];
+        Pentagon::new(f[0], f[1], f[2], f[3], f[4])
+    }
+
+    fn face(&self, idx: usize) -> Pentagon<(f32, f32, f32)> {
+        self.face_indexed(idx).map_vertex(|i| self.vert(i))
+    }
+}
+
+impl Iterator for Dodecahedron {
+    type Item = Pentagon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Pentagon<(f32, f32, f32)>> {
+        self.range.next().map(|idx| self.face(idx))
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Dodecahedron {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        self.vert(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize { 20 }
+}
+
+impl IndexedPolygon<Pentagon<usize>> for Dodecahedron {
+    fn indexed_polygon(&self, idx: usize) -> Pentagon<usize> {
+        self.face_indexed(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize { 12 }
+}
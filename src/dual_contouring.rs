@@ -0,0 +1,241 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::num::Float;
+use super::{Quad, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+use contour::{Grid, Point};
+use math::{Vec3, add, sub, scale, dot, length, normalize};
+
+/// eigenvalues of the QEF below this are treated as 0, so that flat and
+/// nearly flat parts of the surface do not throw the vertex off
+const SINGULAR: f32 = 0.1;
+
+/// the number of times each crossed edge is halved to find the surface
+const BISECTIONS: usize = 16;
+
+/// find the eigenvalues and eigenvectors of the symmetric matrix `a` with
+/// Jacobi rotations, the eigenvectors are the columns of the returned matrix
+fn eigen(mut a: [[f32; 3]; 3]) -> ([f32; 3], [[f32; 3]; 3]) {
+    let mut v = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+    for _ in 0..8 {
+        for &(p, q) in [(0, 1), (0, 2), (1, 2)].iter() {
+            if a[p][q].abs() < 1e-12 {
+                continue;
+            }
+
+            let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+            let c = 1. / (t * t + 1.).sqrt();
+            let s = t * c;
+
+            for k in 0..3 {
+                let (kp, kq) = (a[k][p], a[k][q]);
+                a[k][p] = c * kp - s * kq;
+                a[k][q] = s * kp + c * kq;
+            }
+            for k in 0..3 {
+                let (pk, qk) = (a[p][k], a[q][k]);
+                a[p][k] = c * pk - s * qk;
+                a[q][k] = s * pk + c * qk;
+            }
+            for k in 0..3 {
+                let (kp, kq) = (v[k][p], v[k][q]);
+                v[k][p] = c * kp - s * kq;
+                v[k][q] = s * kp + c * kq;
+            }
+        }
+    }
+    ([a[0][0], a[1][1], a[2][2]], v)
+}
+
+/// the average of `points`
+fn mass_point(points: &[Vec3]) -> Vec3 {
+    let sum = points.iter().fold((0., 0., 0.), |sum, &p| add(sum, p));
+    scale(sum, 1. / points.len() as f32)
+}
+
+/// find the point that is closest to all of the planes through `points`
+/// with `normals`, starting from their average. Directions that the planes
+/// do not constrain are left at the average.
+fn solve_qef(points: &[Vec3], normals: &[Vec3]) -> Vec3 {
+    let mass = mass_point(points);
+
+    // A^T A and A^T (b - A mass), with the planes moved to the mass point
+    let mut ata = [[0.; 3]; 3];
+    let mut atb = [0.; 3];
+    for (&p, &n) in points.iter().zip(normals.iter()) {
+        let n = [n.0, n.1, n.2];
+        let d = dot((n[0], n[1], n[2]), sub(p, mass));
+        for r in 0..3 {
+            for c in 0..3 {
+                ata[r][c] += n[r] * n[c];
+            }
+            atb[r] += n[r] * d;
+        }
+    }
+
+    let (values, vectors) = eigen(ata);
+    let mut x = mass;
+    for i in 0..3 {
+        if values[i] < SINGULAR {
+            continue;
+        }
+        let v = (vectors[0][i], vectors[1][i], vectors[2][i]);
+        let w = dot(v, (atb[0], atb[1], atb[2])) / values[i];
+        x = add(x, scale(v, w));
+    }
+    x
+}
+
+/// Represents the surface where a scalar field crosses an iso-level,
+/// found with dual contouring.
+///
+/// This is laid out in the same way as `SurfaceNets`, but the vertex of
+/// each cell is placed by solving a quadratic error function built from the
+/// gradient of the field at the points where the surface crosses the edges
+/// of the cell. This keeps the sharp edges and corners of the surface.
+#[derive(Clone)]
+pub struct DualContouring {
+    range: Range<usize>,
+    vertices: Vec<Vec3>,
+    faces: Vec<Quad<usize>>
+}
+
+impl DualContouring {
+    /// Create a new surface, the gradient of the field is found with
+    /// central differences.
+    /// `field` is sampled at every point of the grid.
+    /// `min` and `max` are the opposite corners of the grid.
+    /// `resolution` is the number of cells along the x, y and z axes.
+    /// `iso` is the value of the field on the surface.
+    pub fn new<F>(field: F,
+                  min: Vec3,
+                  max: Vec3,
+                  resolution: (usize, usize, usize),
+                  iso: f32) -> DualContouring
+        where F: Fn(f32, f32, f32) -> f32 {
+
+        let grid = Grid::new(&field, min, max, resolution, iso);
+        let h = grid.min_step() * 1e-2;
+        let gradient = |x: f32, y: f32, z: f32| {
+            (field(x + h, y, z) - field(x - h, y, z),
+             field(x, y + h, z) - field(x, y - h, z),
+             field(x, y, z + h) - field(x, y, z - h))
+        };
+        DualContouring::build(&grid, &field, &gradient)
+    }
+
+    /// Create a new surface, with the gradient of the field given by
+    /// `gradient`. See `DualContouring::new`.
+    pub fn with_gradient<F, G>(field: F,
+                               gradient: G,
+                               min: Vec3,
+                               max: Vec3,
+                               resolution: (usize, usize, usize),
+                               iso: f32) -> DualContouring
+        where F: Fn(f32, f32, f32) -> f32,
+              G: Fn(f32, f32, f32) -> Vec3 {
+
+        let grid = Grid::new(&field, min, max, resolution, iso);
+        DualContouring::build(&grid, &field, &gradient)
+    }
+
+    fn build<F, G>(grid: &Grid, field: &F, gradient: &G) -> DualContouring
+        where F: Fn(f32, f32, f32) -> f32,
+              G: Fn(f32, f32, f32) -> Vec3 {
+
+        let (vertices, faces) = grid.contour(|cell: Point| {
+            // the field is not linear along the edges, so the crossings
+            // are found by bisection. The gradient is only meaningful
+            // on the surface.
+            let points: Vec<Vec3> = grid.crossed_edges(cell).iter().map(|&((a, fa), (b, _))| {
+                let (mut inside, mut outside) = if fa < grid.iso() { (a, b) } else { (b, a) };
+                for _ in 0..BISECTIONS {
+                    let mid = scale(add(inside, outside), 0.5);
+                    if field(mid.0, mid.1, mid.2) < grid.iso() {
+                        inside = mid;
+                    } else {
+                        outside = mid;
+                    }
+                }
+                scale(add(inside, outside), 0.5)
+            }).collect();
+
+            // a crossing without a gradient, such as on a crease where the
+            // central differences cancel, has no plane to add to the QEF
+            let mut planes = Vec::with_capacity(points.len());
+            let mut normals = Vec::with_capacity(points.len());
+            for &p in points.iter() {
+                let n = gradient(p.0, p.1, p.2);
+                if length(n) > 0. {
+                    planes.push(p);
+                    normals.push(normalize(n));
+                }
+            }
+            if planes.is_empty() {
+                return mass_point(&points);
+            }
+            let x = solve_qef(&planes, &normals);
+
+            // a vertex that ends up outside of its cell is not trusted,
+            // this is written so that a NaN is not trusted either
+            let (lo, hi) = (grid.point(cell), grid.point((cell.0 + 1, cell.1 + 1, cell.2 + 1)));
+            let e = grid.min_step() * 1e-3;
+            if x.0 >= lo.0 - e && x.1 >= lo.1 - e && x.2 >= lo.2 - e &&
+               x.0 <= hi.0 + e && x.1 <= hi.1 + e && x.2 <= hi.2 + e {
+                x
+            } else {
+                mass_point(&points)
+            }
+        });
+
+        DualContouring {
+            range: 0..faces.len(),
+            vertices: vertices,
+            faces: faces
+        }
+    }
+}
+
+impl Iterator for DualContouring {
+    type Item = Quad<Vec3>;
+
+    fn next(&mut self) -> Option<Quad<Vec3>> {
+        self.range.next().map(|idx| {
+            self.faces[idx].map_vertex(|i| self.vertices[i])
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for DualContouring {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for DualContouring {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        self.faces[idx]
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+}
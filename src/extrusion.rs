@@ -0,0 +1,126 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{Quad, Triangle, Polygon, MapVertex};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use triangulate::{signed_area, triangulate_polygon};
+
+/// Represents a prism built by extruding a 2D outline along the z axis,
+/// the bottom of the prism is at z = 0.
+///
+/// The caps and the sides do not share any vertices, so the
+/// edges between them stay hard.
+#[derive(Clone)]
+pub struct Extrusion {
+    range: Range<usize>,
+    outline: Vec<(f32, f32)>,
+    depth: f32,
+    sub_d: usize,
+    cap: Vec<Triangle<usize>>
+}
+
+impl Extrusion {
+    /// Create a new extrusion.
+    /// `outline` is a closed simple polygon in the xy plane, it may be concave
+    /// and may be wound in either direction.
+    /// `depth` is the distance the outline is extruded along the z axis.
+    /// `segments` is the number of subdivisions along the depth.
+    pub fn new(outline: Vec<(f32, f32)>, depth: f32, segments: usize) -> Extrusion {
+        assert!(outline.len() > 2 && segments > 0);
+        let mut outline = outline;
+        if signed_area(&outline) < 0. {
+            outline.reverse();
+        }
+        let cap = triangulate_polygon(&outline);
+
+        let mut extrusion = Extrusion {
+            range: 0..0,
+            outline: outline,
+            depth: depth,
+            sub_d: segments,
+            cap: cap
+        };
+        extrusion.range = 0..extrusion.indexed_polygon_count();
+        extrusion
+    }
+
+    fn side_count(&self) -> usize {
+        (self.sub_d + 1) * self.outline.len()
+    }
+
+    fn side_index(&self, i: usize, d: usize) -> usize {
+        d * self.outline.len() + (i % self.outline.len())
+    }
+}
+
+impl Iterator for Extrusion {
+    type Item = Polygon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Polygon<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Extrusion {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        let n = self.outline.len();
+        let (i, z) = if idx < self.side_count() {
+            (idx % n, self.depth * (idx / n) as f32 / self.sub_d as f32)
+        } else if idx < self.side_count() + n {
+            (idx - self.side_count(), 0.)
+        } else {
+            (idx - self.side_count() - n, self.depth)
+        };
+
+        let (x, y) = self.outline[i];
+        (x, y, z)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.side_count() + 2 * self.outline.len()
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Extrusion {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let n = self.outline.len();
+        let caps = self.cap.len();
+        let bottom = self.side_count();
+        let top = bottom + n;
+
+        if idx < caps {
+            let Triangle{x, y, z} = self.cap[idx];
+            PolyTri(Triangle::new(bottom + z, bottom + y, bottom + x))
+        } else if idx < caps + self.sub_d * n {
+            let idx = idx - caps;
+            let i = idx % n;
+            let d = idx / n;
+            PolyQuad(Quad::new(self.side_index(i,   d),
+                               self.side_index(i+1, d),
+                               self.side_index(i+1, d+1),
+                               self.side_index(i,   d+1)))
+        } else {
+            let Triangle{x, y, z} = self.cap[idx - caps - self.sub_d * n];
+            PolyTri(Triangle::new(top + x, top + y, top + z))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        2 * self.cap.len() + self.sub_d * self.outline.len()
+    }
+}
@@ -0,0 +1,121 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::num::Float;
+use super::{MapVertex, Quad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use plane::Plane;
+
+/// Represents a terrain built from a grid of height samples. The grid covers
+/// the same area as `Plane`, from 1 to -1 in x and y, and the height is along
+/// the z axis.
+#[derive(Clone)]
+pub struct Heightmap {
+    range: Range<usize>,
+    plane: Plane,
+    samples: Vec<f32>,
+    horizontal: f32,
+    vertical: f32,
+    best_diagonal: bool
+}
+
+impl Heightmap {
+    /// Create a new heightmap.
+    /// `width` is the number of samples along the x axis
+    /// `height` is the number of samples along the y axis
+    /// `samples` is the `width` * `height` height samples, row by row
+    pub fn new(width: usize, height: usize, samples: Vec<f32>) -> Heightmap {
+        assert!(width > 1 && height > 1);
+        assert!(samples.len() == width * height);
+        let plane = Plane::subdivide(width - 1, height - 1);
+        Heightmap {
+            range: 0..plane.indexed_polygon_count(),
+            plane: plane,
+            samples: samples,
+            horizontal: 1.,
+            vertical: 1.,
+            best_diagonal: false
+        }
+    }
+
+    /// Create a new heightmap by sampling `f` at each point of the grid.
+    /// `f` is given the x and y position on the unscaled grid.
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Heightmap
+        where F: Fn(f32, f32) -> f32 {
+
+        assert!(width > 1 && height > 1);
+        let plane = Plane::subdivide(width - 1, height - 1);
+        let samples = plane.shared_vertex_iter().map(|(x, y)| f(x, y)).collect();
+        Heightmap::new(width, height, samples)
+    }
+
+    /// Scale the grid by `horizontal` in x and y, and the samples by `vertical`.
+    pub fn scale(mut self, horizontal: f32, vertical: f32) -> Heightmap {
+        self.horizontal = horizontal;
+        self.vertical = vertical;
+        self
+    }
+
+    /// Pick the diagonal of each quad that best follows the surface. The
+    /// quad is rotated so that it will be split along the shorter of its
+    /// two diagonals by `triangulate`.
+    pub fn best_diagonal(mut self, enable: bool) -> Heightmap {
+        self.best_diagonal = enable;
+        self
+    }
+}
+
+impl Iterator for Heightmap {
+    type Item = Quad<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Quad<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Heightmap {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        let (x, y) = self.plane.shared_vertex(idx);
+        (x * self.horizontal,
+         y * self.horizontal,
+         self.samples[idx] * self.vertical)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.plane.shared_vertex_count()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for Heightmap {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let quad = self.plane.indexed_polygon(idx);
+        if !self.best_diagonal {
+            return quad;
+        }
+
+        let h = |i: usize| self.samples[i];
+        if (h(quad.y) - h(quad.w)).abs() < (h(quad.x) - h(quad.z)).abs() {
+            Quad::new(quad.y, quad.z, quad.w, quad.x)
+        } else {
+            quad
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.plane.indexed_polygon_count()
+    }
+}
@@ -0,0 +1,115 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::collections::HashMap;
+use std::num::Float;
+use super::{MapVertex, Hexagon};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// the corners of a hexagon, in units of half of the hexagon's
+/// width along x and half of its edge length along y
+const CORNERS: [(isize, isize); 6] = [
+    (1, 1), (0, 2), (-1, 1), (-1, -1), (0, -2), (1, -1)
+];
+
+/// Represents a 2D grid of pointy topped hexagons with an edge length of 1,
+/// the center of the first hexagon is at (0, 0). Every other row is shifted
+/// by half of a hexagon along the x axis.
+#[derive(Clone)]
+pub struct HexGrid {
+    range: Range<usize>,
+    width: usize,
+    vertices: Vec<(f32, f32)>,
+    faces: Vec<Hexagon<usize>>
+}
+
+impl HexGrid {
+    /// Create a new hexagon grid.
+    /// `x` is the number of hexagons in each row
+    /// `y` is the number of rows
+    pub fn new(x: usize, y: usize) -> HexGrid {
+        assert!(x > 0 && y > 0);
+        let mut lookup = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut faces = Vec::with_capacity(x * y);
+
+        for row in 0..y {
+            for column in 0..x {
+                let cx = (2 * column + row % 2) as isize;
+                let cy = (3 * row) as isize;
+
+                let mut corner = |i: usize| {
+                    let (dx, dy) = CORNERS[i];
+                    let key = (cx + dx, cy + dy);
+                    if let Some(&idx) = lookup.get(&key) {
+                        return idx;
+                    }
+                    let idx = vertices.len();
+                    vertices.push((key.0 as f32 * 3f32.sqrt() * 0.5,
+                                   key.1 as f32 * 0.5));
+                    lookup.insert(key, idx);
+                    idx
+                };
+
+                let (a, b, c) = (corner(0), corner(1), corner(2));
+                let (d, e, f) = (corner(3), corner(4), corner(5));
+                faces.push(Hexagon::new(a, b, c, d, e, f));
+            }
+        }
+
+        HexGrid {
+            range: 0..faces.len(),
+            width: x,
+            vertices: vertices,
+            faces: faces
+        }
+    }
+
+    /// Find the grid coordinates of polygon `idx`, this is the
+    /// column of the hexagon followed by its row.
+    pub fn grid_coordinates(&self, idx: usize) -> (usize, usize) {
+        (idx % self.width, idx / self.width)
+    }
+}
+
+impl Iterator for HexGrid {
+    type Item = Hexagon<(f32, f32)>;
+
+    fn next(&mut self) -> Option<Hexagon<(f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.faces[idx].map_vertex(|i| self.vertices[i])
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32)> for HexGrid {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32) {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Hexagon<usize>> for HexGrid {
+    fn indexed_polygon(&self, idx: usize) -> Hexagon<usize> {
+        self.faces[idx]
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+}
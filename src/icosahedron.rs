@@ -0,0 +1,69 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{MapVertex, Triangle};
+use super::generators::{SharedVertex, IndexedPolygon};
+use icosphere::{VERTICES, FACES};
+use math::normalize;
+
+/// A regular icosahedron, centered at (0, 0, 0) with each vertex 1 away from the origin
+#[derive(Clone)]
+pub struct Icosahedron {
+    range: Range<usize>
+}
+
+impl Icosahedron {
+    /// create a new icosahedron generator
+    pub fn new() -> Icosahedron {
+        Icosahedron { range: 0..20 }
+    }
+
+    fn vert(&self, idx: usize) -> (f32, f32, f32) {
+        normalize(VERTICES[idx])
+    }
+
+    fn face_indexed(&self, idx: usize) -> Triangle<usize> {
+        let f = FACES[idx];
+        Triangle::new(f[0], f[1], f[2])
+    }
+
+    fn face(&self, idx: usize) -> Triangle<(f32, f32, f32)> {
+        self.face_indexed(idx).map_vertex(|i| self.vert(i))
+    }
+}
+
+impl Iterator for Icosahedron {
+    type Item = Triangle<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Triangle<(f32, f32, f32)>> {
+        self.range.next().map(|idx| self.face(idx))
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Icosahedron {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        self.vert(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize { 12 }
+}
+
+impl IndexedPolygon<Triangle<usize>> for Icosahedron {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        self.face_indexed(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize { 20 }
+}
@@ -0,0 +1,133 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::collections::HashMap;
+use super::{Triangle, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+use math::normalize;
+
+/// the golden ratio
+const PHI: f32 = 1.618034;
+
+/// the vertices of an icosahedron, these are not yet normalized
+pub const VERTICES: [(f32, f32, f32); 12] = [
+    (-1.,  PHI,  0.), ( 1.,  PHI,  0.), (-1., -PHI,  0.), ( 1., -PHI,  0.),
+    ( 0., -1.,  PHI), ( 0.,  1.,  PHI), ( 0., -1., -PHI), ( 0.,  1., -PHI),
+    ( PHI,  0., -1.), ( PHI,  0.,  1.), (-PHI,  0., -1.), (-PHI,  0.,  1.)
+];
+
+/// the faces of an icosahedron, wound counter-clockwise when
+/// viewed from outside
+pub const FACES: [[usize; 3]; 20] = [
+    [0, 11,  5], [0,  5,  1], [0,  1,  7], [0,  7, 10], [0, 10, 11],
+    [1,  5,  9], [5, 11,  4], [11, 10, 2], [10, 7,  6], [7,  1,  8],
+    [3,  9,  4], [3,  4,  2], [3,  2,  6], [3,  6,  8], [3,  8,  9],
+    [4,  9,  5], [2,  4, 11], [6,  2, 10], [8,  6,  7], [9,  8,  1]
+];
+
+/// Represents a sphere with radius of 1, centered at (0, 0, 0), built by
+/// subdividing the faces of an icosahedron.
+#[derive(Clone)]
+pub struct IcoSphere {
+    range: Range<usize>,
+    vertices: Vec<(f32, f32, f32)>,
+    faces: Vec<Triangle<usize>>
+}
+
+impl IcoSphere {
+    /// Create a new icosphere, with no subdivisions this is
+    /// an icosahedron.
+    pub fn new() -> IcoSphere {
+        IcoSphere::subdivide(0)
+    }
+
+    /// Create a new icosphere, each subdivision splits every triangle into
+    /// four, the new vertices are projected onto the sphere.
+    /// `n` is the number of times to subdivide the icosahedron.
+    pub fn subdivide(n: usize) -> IcoSphere {
+        let mut vertices: Vec<(f32, f32, f32)> =
+            VERTICES.iter().map(|&v| normalize(v)).collect();
+        let mut faces: Vec<Triangle<usize>> =
+            FACES.iter().map(|f| Triangle::new(f[0], f[1], f[2])).collect();
+
+        for _ in 0..n {
+            let mut midpoints = HashMap::new();
+            let mut next = Vec::with_capacity(faces.len() * 4);
+
+            {
+                let mut midpoint = |a: usize, b: usize| {
+                    let key = if a < b { (a, b) } else { (b, a) };
+                    if let Some(&idx) = midpoints.get(&key) {
+                        return idx;
+                    }
+                    let (ax, ay, az) = vertices[a];
+                    let (bx, by, bz) = vertices[b];
+                    let idx = vertices.len();
+                    vertices.push(normalize((ax + bx, ay + by, az + bz)));
+                    midpoints.insert(key, idx);
+                    idx
+                };
+
+                for &Triangle{x, y, z} in faces.iter() {
+                    let a = midpoint(x, y);
+                    let b = midpoint(y, z);
+                    let c = midpoint(z, x);
+                    next.push(Triangle::new(x, a, c));
+                    next.push(Triangle::new(y, b, a));
+                    next.push(Triangle::new(z, c, b));
+                    next.push(Triangle::new(a, b, c));
+                }
+            }
+
+            faces = next;
+        }
+
+        IcoSphere {
+            range: 0..faces.len(),
+            vertices: vertices,
+            faces: faces
+        }
+    }
+}
+
+impl Iterator for IcoSphere {
+    type Item = Triangle<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Triangle<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.faces[idx].map_vertex(|i| self.vertices[i])
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for IcoSphere {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Triangle<usize>> for IcoSphere {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        self.faces[idx]
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+}
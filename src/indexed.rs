@@ -0,0 +1,67 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::MapVertex;
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// a list of vertices and the polygons that index them, this holds the
+/// mesh of the generators that have to be built up front
+#[derive(Clone)]
+pub struct Indexed<V, P> {
+    range: Range<usize>,
+    vertices: Vec<V>,
+    polygons: Vec<P>
+}
+
+impl<V, P> Indexed<V, P> {
+    /// create a new mesh from its vertices and polygons
+    pub fn new(vertices: Vec<V>, polygons: Vec<P>) -> Indexed<V, P> {
+        Indexed {
+            range: 0..polygons.len(),
+            vertices: vertices,
+            polygons: polygons
+        }
+    }
+}
+
+impl<V: Clone, P: Clone + MapVertex<usize, V>> Iterator for Indexed<V, P> {
+    type Item = <P as MapVertex<usize, V>>::Output;
+
+    fn next(&mut self) -> Option<<P as MapVertex<usize, V>>::Output> {
+        self.range.next().map(|idx| {
+            self.polygons[idx].clone().map_vertex(|i| self.vertices[i].clone())
+        })
+    }
+}
+
+impl<V: Clone, P> SharedVertex<V> for Indexed<V, P> {
+    fn shared_vertex(&self, idx: usize) -> V {
+        self.vertices[idx].clone()
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl<V, P: Clone> IndexedPolygon<P> for Indexed<V, P> {
+    fn indexed_polygon(&self, idx: usize) -> P {
+        self.polygons[idx].clone()
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.polygons.len()
+    }
+}
@@ -0,0 +1,153 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{Quad, Triangle, Polygon, MapVertex};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a surface of revolution, built by spinning a 2D profile
+/// around the z axis.
+#[derive(Clone)]
+pub struct Lathe {
+    range: Range<usize>,
+    profile: Vec<(f32, f32)>,
+    sub_u: usize,
+    angle: f32,
+    // the first shared vertex of each point of the profile
+    offsets: Vec<usize>,
+    // the profile point at the start of each band of polygons, segments
+    // that lie on the axis do not produce any polygons
+    bands: Vec<usize>
+}
+
+impl Lathe {
+    /// Create a new lathe that sweeps the profile a full circle.
+    /// `profile` is a list of (distance from the axis, height) points, a profile
+    /// running from bottom to top produces outward facing polygons.
+    /// `u` is the number of segments around the axis.
+    pub fn new(profile: Vec<(f32, f32)>, u: usize) -> Lathe {
+        assert!(profile.len() > 1 && u > 1);
+        let mut lathe = Lathe {
+            range: 0..0,
+            profile: profile,
+            sub_u: u,
+            angle: PI_2,
+            offsets: Vec::new(),
+            bands: Vec::new()
+        };
+        lathe.build();
+        lathe
+    }
+
+    /// Only sweep the profile `angle` radians around the axis. If this is
+    /// less then a full circle the first and last column of vertices are no
+    /// longer shared.
+    pub fn sweep(mut self, angle: f32) -> Lathe {
+        self.angle = angle;
+        self.build();
+        self
+    }
+
+    fn build(&mut self) {
+        let mut offset = 0;
+        self.offsets.clear();
+        for p in 0..self.profile.len() {
+            self.offsets.push(offset);
+            offset += if self.on_axis(p) { 1 } else { self.columns() };
+        }
+
+        self.bands = (0..self.profile.len() - 1)
+            .filter(|&p| !(self.on_axis(p) && self.on_axis(p + 1)))
+            .collect();
+        self.range = 0..self.indexed_polygon_count();
+    }
+
+    fn full(&self) -> bool {
+        self.angle >= PI_2
+    }
+
+    fn columns(&self) -> usize {
+        if self.full() { self.sub_u } else { self.sub_u + 1 }
+    }
+
+    fn on_axis(&self, p: usize) -> bool {
+        self.profile[p].0 == 0.
+    }
+
+    fn vert(&self, p: usize, i: usize) -> (f32, f32, f32) {
+        let a = (i as f32 / self.sub_u as f32) * self.angle.min(PI_2);
+        let (r, z) = self.profile[p];
+        (r * a.cos(), r * a.sin(), z)
+    }
+
+    fn index(&self, p: usize, i: usize) -> usize {
+        if self.on_axis(p) {
+            self.offsets[p]
+        } else {
+            self.offsets[p] + (i % self.columns())
+        }
+    }
+}
+
+impl Iterator for Lathe {
+    type Item = Polygon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Polygon<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Lathe {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        let p = match self.offsets.binary_search(&idx) {
+            Ok(p) => p,
+            Err(p) => p - 1
+        };
+        self.vert(p, idx - self.offsets[p])
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        let last = self.profile.len() - 1;
+        self.index(last, self.columns() - 1) + 1
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Lathe {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let p = self.bands[idx / self.sub_u];
+        let i = idx % self.sub_u;
+
+        let x = self.index(p,   i);
+        let y = self.index(p,   i+1);
+        let z = self.index(p+1, i+1);
+        let w = self.index(p+1, i);
+
+        if self.on_axis(p) {
+            PolyTri(Triangle::new(x, z, w))
+        } else if self.on_axis(p+1) {
+            PolyTri(Triangle::new(x, y, z))
+        } else {
+            PolyQuad(Quad::new(x, y, z, w))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.bands.len() * self.sub_u
+    }
+}
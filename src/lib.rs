@@ -32,6 +32,8 @@
 pub use poly::{
     Quad,
     Triangle,
+    Pentagon,
+    Hexagon,
     Polygon,
     Vertices,
     VerticesIterator,
@@ -50,14 +52,51 @@ pub use indexer::{
     LruIndexer
 };
 
+pub use normals::Vertex;
+pub use textured::TexturedVertex;
+
 mod triangulate;
 mod poly;
 mod indexer;
 mod generator;
+mod math;
+mod indexed;
+mod normals;
+mod textured;
+mod contour;
 
 mod cube;
 mod plane;
 mod sphere;
+mod cylinder;
+mod torus;
+mod icosphere;
+mod cone;
+mod capsule;
+mod tetrahedron;
+mod octahedron;
+mod dodecahedron;
+mod icosahedron;
+mod circle;
+mod annulus;
+mod parametric;
+mod lathe;
+mod extrusion;
+mod tube;
+mod heightmap;
+mod triangle_grid;
+mod hex_grid;
+mod rounded_box;
+mod torus_knot;
+mod superellipsoid;
+mod supertoroid;
+mod cube_sphere;
+mod bezier_patch;
+mod teapot;
+mod marching_cubes;
+mod surface_nets;
+mod dual_contouring;
+mod voxel;
 
 /// a collection of utilties that can be used to build
 /// meshes programmatically
@@ -71,4 +110,35 @@ pub mod generators {
     pub use cube::Cube;
     pub use plane::Plane;
     pub use sphere::SphereUV;
+    pub use cylinder::Cylinder;
+    pub use torus::Torus;
+    pub use icosphere::IcoSphere;
+    pub use cone::Cone;
+    pub use capsule::Capsule;
+    pub use tetrahedron::Tetrahedron;
+    pub use octahedron::Octahedron;
+    pub use dodecahedron::Dodecahedron;
+    pub use icosahedron::Icosahedron;
+    pub use circle::Circle;
+    pub use annulus::Annulus;
+    pub use parametric::ParametricSurface;
+    pub use lathe::Lathe;
+    pub use extrusion::Extrusion;
+    pub use tube::Tube;
+    pub use heightmap::Heightmap;
+    pub use triangle_grid::TriangleGrid;
+    pub use hex_grid::HexGrid;
+    pub use rounded_box::RoundedBox;
+    pub use torus_knot::TorusKnot;
+    pub use superellipsoid::Superellipsoid;
+    pub use supertoroid::Supertoroid;
+    pub use cube_sphere::CubeSphere;
+    pub use bezier_patch::BezierPatch;
+    pub use teapot::Teapot;
+    pub use marching_cubes::MarchingCubes;
+    pub use surface_nets::SurfaceNets;
+    pub use dual_contouring::DualContouring;
+    pub use voxel::{Voxels, VoxelVertex};
+    pub use normals::Normals;
+    pub use textured::Textured;
 }
@@ -0,0 +1,438 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::collections::HashMap;
+use super::{Triangle, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+use math::{Vec3, add, sub, scale};
+
+/// the corners of a cell, bit `n` of a cell's case is set when corner
+/// `n` is inside the surface
+const CORNERS: [(usize, usize, usize); 8] = [
+    (0, 0, 0), (1, 0, 0), (1, 1, 0), (0, 1, 0),
+    (0, 0, 1), (1, 0, 1), (1, 1, 1), (0, 1, 1)
+];
+
+/// the edges of a cell, as pairs of corners
+const EDGES: [(usize, usize); 12] = [
+    (0, 1), (1, 2), (2, 3), (3, 0),
+    (4, 5), (5, 6), (6, 7), (7, 4),
+    (0, 4), (1, 5), (2, 6), (3, 7)
+];
+
+/// the triangles of each case, as edges of the cell, ended by -1.
+///
+/// The table was built by tracing the surface around the faces of the
+/// cell. A face with two inside corners on a diagonal always keeps the
+/// inside corners apart, since both cells that share a face see the same
+/// corners they always agree, so the surface has no cracks. The triangles
+/// are wound counter-clockwise when viewed from outside of the surface.
+const TRIANGLES: [[i8; 16]; 256] = [
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1,  3,  8,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 10,  2,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  0,  9, 10,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9, 10,  2,  8,  9,  2,  3,  8,  2, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0,  2, 11,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0, 11,  3,  2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1, 11,  8,  1,  2, 11,  1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  1, 10, 11,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0, 10, 11,  0,  1, 10,  0, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  0, 10, 11,  0,  9, 10,  0, -1, -1, -1, -1, -1, -1, -1],
+    [10, 11,  8,  9, 10,  8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  7,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0,  3,  7,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  9,  1,  7,  4,  1,  3,  7,  1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  1,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0,  3,  7,  0, 10,  2,  1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  0,  9, 10,  0,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 9, 10,  2,  4,  9,  2,  7,  4,  2,  3,  7,  2, -1, -1, -1, -1],
+    [11,  3,  2,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0, 11,  7,  0,  2, 11,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0, 11,  3,  2,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  9,  1,  7,  4,  1, 11,  7,  1,  2, 11,  1, -1, -1, -1, -1],
+    [11,  3,  1, 10, 11,  1,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0, 11,  7,  0, 10, 11,  0,  1, 10,  0, -1, -1, -1, -1],
+    [11,  3,  0, 10, 11,  0,  9, 10,  0,  8,  7,  4, -1, -1, -1, -1],
+    [11,  7,  4, 10, 11,  4,  9, 10,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  9,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  1,  0,  4,  5,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  5,  1,  8,  4,  1,  3,  8,  1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  1,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 10,  2,  1,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  0,  5, 10,  0,  4,  5,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 5, 10,  2,  4,  5,  2,  8,  4,  2,  3,  8,  2, -1, -1, -1, -1],
+    [11,  3,  2,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0,  2, 11,  0,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  1,  0,  4,  5,  0, 11,  3,  2, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  5,  1,  8,  4,  1, 11,  8,  1,  2, 11,  1, -1, -1, -1, -1],
+    [11,  3,  1, 10, 11,  1,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0, 10, 11,  0,  1, 10,  0,  5,  9,  4, -1, -1, -1, -1],
+    [11,  3,  0, 10, 11,  0,  5, 10,  0,  4,  5,  0, -1, -1, -1, -1],
+    [11,  8,  4, 10, 11,  4,  5, 10,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  7,  5,  9,  8,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  9,  0,  7,  5,  0,  3,  7,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  1,  0,  7,  5,  0,  8,  7,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  5,  1,  3,  7,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  1,  8,  7,  5,  9,  8,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  9,  0,  7,  5,  0,  3,  7,  0, 10,  2,  1, -1, -1, -1, -1],
+    [10,  2,  0,  5, 10,  0,  7,  5,  0,  8,  7,  0, -1, -1, -1, -1],
+    [ 5, 10,  2,  7,  5,  2,  3,  7,  2, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  2,  8,  7,  5,  9,  8,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  9,  0,  7,  5,  0, 11,  7,  0,  2, 11,  0, -1, -1, -1, -1],
+    [ 5,  1,  0,  7,  5,  0,  8,  7,  0, 11,  3,  2, -1, -1, -1, -1],
+    [ 7,  5,  1, 11,  7,  1,  2, 11,  1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  1, 10, 11,  1,  8,  7,  5,  9,  8,  5, -1, -1, -1, -1],
+    [ 5,  9,  0,  7,  5,  0, 11,  7,  0, 10, 11,  0,  1, 10,  0, -1],
+    [11,  3,  0, 10, 11,  0,  5, 10,  0,  7,  5,  0,  8,  7,  0, -1],
+    [11,  7,  5, 10, 11,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 6, 10,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1,  3,  8,  1,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 6,  2,  1,  5,  6,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0,  6,  2,  1,  5,  6,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 6,  2,  0,  5,  6,  0,  9,  5,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  6,  2,  9,  5,  2,  8,  9,  2,  3,  8,  2, -1, -1, -1, -1],
+    [11,  3,  2,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0,  2, 11,  0,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0, 11,  3,  2,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1, 11,  8,  1,  2, 11,  1,  6, 10,  5, -1, -1, -1, -1],
+    [11,  3,  1,  6, 11,  1,  5,  6,  1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0,  6, 11,  0,  5,  6,  0,  1,  5,  0, -1, -1, -1, -1],
+    [11,  3,  0,  6, 11,  0,  5,  6,  0,  9,  5,  0, -1, -1, -1, -1],
+    [ 8,  9,  5, 11,  8,  5,  6, 11,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  7,  4,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0,  3,  7,  0,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  8,  7,  4,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  9,  1,  7,  4,  1,  3,  7,  1,  6, 10,  5, -1, -1, -1, -1],
+    [ 6,  2,  1,  5,  6,  1,  8,  7,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0,  3,  7,  0,  6,  2,  1,  5,  6,  1, -1, -1, -1, -1],
+    [ 6,  2,  0,  5,  6,  0,  9,  5,  0,  8,  7,  4, -1, -1, -1, -1],
+    [ 5,  6,  2,  9,  5,  2,  4,  9,  2,  7,  4,  2,  3,  7,  2, -1],
+    [11,  3,  2,  8,  7,  4,  6, 10,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  4,  0, 11,  7,  0,  2, 11,  0,  6, 10,  5, -1, -1, -1, -1],
+    [ 9,  1,  0, 11,  3,  2,  8,  7,  4,  6, 10,  5, -1, -1, -1, -1],
+    [ 4,  9,  1,  7,  4,  1, 11,  7,  1,  2, 11,  1,  6, 10,  5, -1],
+    [11,  3,  1,  6, 11,  1,  5,  6,  1,  8,  7,  4, -1, -1, -1, -1],
+    [ 7,  4,  0, 11,  7,  0,  6, 11,  0,  5,  6,  0,  1,  5,  0, -1],
+    [11,  3,  0,  6, 11,  0,  5,  6,  0,  9,  5,  0,  8,  7,  4, -1],
+    [ 5,  6, 11,  9,  5, 11,  4,  9, 11,  7,  4, 11, -1, -1, -1, -1],
+    [10,  9,  4,  6, 10,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 10,  9,  4,  6, 10,  4, -1, -1, -1, -1, -1, -1, -1],
+    [10,  1,  0,  6, 10,  0,  4,  6,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 6, 10,  1,  4,  6,  1,  8,  4,  1,  3,  8,  1, -1, -1, -1, -1],
+    [ 6,  2,  1,  4,  6,  1,  9,  4,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0,  6,  2,  1,  4,  6,  1,  9,  4,  1, -1, -1, -1, -1],
+    [ 6,  2,  0,  4,  6,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  6,  2,  8,  4,  2,  3,  8,  2, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  2, 10,  9,  4,  6, 10,  4, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  0,  2, 11,  0, 10,  9,  4,  6, 10,  4, -1, -1, -1, -1],
+    [10,  1,  0,  6, 10,  0,  4,  6,  0, 11,  3,  2, -1, -1, -1, -1],
+    [ 6, 10,  1,  4,  6,  1,  8,  4,  1, 11,  8,  1,  2, 11,  1, -1],
+    [11,  3,  1,  6, 11,  1,  4,  6,  1,  9,  4,  1, -1, -1, -1, -1],
+    [ 4,  6, 11,  9,  4, 11,  1,  9, 11,  0,  1, 11,  8,  0, 11, -1],
+    [11,  3,  0,  6, 11,  0,  4,  6,  0, -1, -1, -1, -1, -1, -1, -1],
+    [11,  8,  4,  6, 11,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  7,  6,  9,  8,  6, 10,  9,  6, -1, -1, -1, -1, -1, -1, -1],
+    [10,  9,  0,  6, 10,  0,  7,  6,  0,  3,  7,  0, -1, -1, -1, -1],
+    [10,  1,  0,  6, 10,  0,  7,  6,  0,  8,  7,  0, -1, -1, -1, -1],
+    [ 6, 10,  1,  7,  6,  1,  3,  7,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 6,  2,  1,  7,  6,  1,  8,  7,  1,  9,  8,  1, -1, -1, -1, -1],
+    [ 2,  1,  9,  6,  2,  9,  7,  6,  9,  3,  7,  9,  0,  3,  9, -1],
+    [ 6,  2,  0,  7,  6,  0,  8,  7,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  6,  2,  3,  7,  2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  2,  8,  7,  6,  9,  8,  6, 10,  9,  6, -1, -1, -1, -1],
+    [10,  9,  0,  6, 10,  0,  7,  6,  0, 11,  7,  0,  2, 11,  0, -1],
+    [10,  1,  0,  6, 10,  0,  7,  6,  0,  8,  7,  0, 11,  3,  2, -1],
+    [ 6, 10,  1,  7,  6,  1, 11,  7,  1,  2, 11,  1, -1, -1, -1, -1],
+    [11,  3,  1,  6, 11,  1,  7,  6,  1,  8,  7,  1,  9,  8,  1, -1],
+    [ 1,  9,  0, 11,  7,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  3,  0,  6, 11,  0,  7,  6,  0,  8,  7,  0, -1, -1, -1, -1],
+    [11,  7,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7, 11,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1,  3,  8,  1,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  1,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 10,  2,  1,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  0,  9, 10,  0,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1],
+    [ 9, 10,  2,  8,  9,  2,  3,  8,  2,  7, 11,  6, -1, -1, -1, -1],
+    [ 7,  3,  2,  6,  7,  2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  8,  0,  6,  7,  0,  2,  6,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  7,  3,  2,  6,  7,  2, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1,  7,  8,  1,  6,  7,  1,  2,  6,  1, -1, -1, -1, -1],
+    [ 7,  3,  1,  6,  7,  1, 10,  6,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  8,  0,  6,  7,  0, 10,  6,  0,  1, 10,  0, -1, -1, -1, -1],
+    [ 7,  3,  0,  6,  7,  0, 10,  6,  0,  9, 10,  0, -1, -1, -1, -1],
+    [ 9, 10,  6,  8,  9,  6,  7,  8,  6, -1, -1, -1, -1, -1, -1, -1],
+    [11,  6,  4,  8, 11,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 6,  4,  0, 11,  6,  0,  3, 11,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0, 11,  6,  4,  8, 11,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  9,  1,  6,  4,  1, 11,  6,  1,  3, 11,  1, -1, -1, -1, -1],
+    [10,  2,  1, 11,  6,  4,  8, 11,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 6,  4,  0, 11,  6,  0,  3, 11,  0, 10,  2,  1, -1, -1, -1, -1],
+    [10,  2,  0,  9, 10,  0, 11,  6,  4,  8, 11,  4, -1, -1, -1, -1],
+    [ 6,  4,  9, 11,  6,  9,  3, 11,  9,  2,  3,  9, 10,  2,  9, -1],
+    [ 8,  3,  2,  4,  8,  2,  6,  4,  2, -1, -1, -1, -1, -1, -1, -1],
+    [ 6,  4,  0,  2,  6,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  8,  3,  2,  4,  8,  2,  6,  4,  2, -1, -1, -1, -1],
+    [ 4,  9,  1,  6,  4,  1,  2,  6,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  3,  1,  4,  8,  1,  6,  4,  1, 10,  6,  1, -1, -1, -1, -1],
+    [ 6,  4,  0, 10,  6,  0,  1, 10,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  8,  3,  6,  4,  3, 10,  6,  3,  9, 10,  3,  0,  9,  3, -1],
+    [10,  6,  4,  9, 10,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  9,  4,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0,  5,  9,  4,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  1,  0,  4,  5,  0,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  5,  1,  8,  4,  1,  3,  8,  1,  7, 11,  6, -1, -1, -1, -1],
+    [10,  2,  1,  5,  9,  4,  7, 11,  6, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 10,  2,  1,  5,  9,  4,  7, 11,  6, -1, -1, -1, -1],
+    [10,  2,  0,  5, 10,  0,  4,  5,  0,  7, 11,  6, -1, -1, -1, -1],
+    [ 5, 10,  2,  4,  5,  2,  8,  4,  2,  3,  8,  2,  7, 11,  6, -1],
+    [ 7,  3,  2,  6,  7,  2,  5,  9,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  8,  0,  6,  7,  0,  2,  6,  0,  5,  9,  4, -1, -1, -1, -1],
+    [ 5,  1,  0,  4,  5,  0,  7,  3,  2,  6,  7,  2, -1, -1, -1, -1],
+    [ 4,  5,  1,  8,  4,  1,  7,  8,  1,  6,  7,  1,  2,  6,  1, -1],
+    [ 7,  3,  1,  6,  7,  1, 10,  6,  1,  5,  9,  4, -1, -1, -1, -1],
+    [ 7,  8,  0,  6,  7,  0, 10,  6,  0,  1, 10,  0,  5,  9,  4, -1],
+    [ 7,  3,  0,  6,  7,  0, 10,  6,  0,  5, 10,  0,  4,  5,  0, -1],
+    [ 6,  7,  8, 10,  6,  8,  5, 10,  8,  4,  5,  8, -1, -1, -1, -1],
+    [11,  6,  5,  8, 11,  5,  9,  8,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  9,  0,  6,  5,  0, 11,  6,  0,  3, 11,  0, -1, -1, -1, -1],
+    [ 5,  1,  0,  6,  5,  0, 11,  6,  0,  8, 11,  0, -1, -1, -1, -1],
+    [ 6,  5,  1, 11,  6,  1,  3, 11,  1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  2,  1, 11,  6,  5,  8, 11,  5,  9,  8,  5, -1, -1, -1, -1],
+    [ 5,  9,  0,  6,  5,  0, 11,  6,  0,  3, 11,  0, 10,  2,  1, -1],
+    [10,  2,  0,  5, 10,  0,  6,  5,  0, 11,  6,  0,  8, 11,  0, -1],
+    [11,  6,  5,  3, 11,  5,  2,  3,  5, 10,  2,  5, -1, -1, -1, -1],
+    [ 8,  3,  2,  9,  8,  2,  5,  9,  2,  6,  5,  2, -1, -1, -1, -1],
+    [ 5,  9,  0,  6,  5,  0,  2,  6,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 2,  6,  5,  3,  2,  5,  8,  3,  5,  0,  8,  5,  1,  0,  5, -1],
+    [ 6,  5,  1,  2,  6,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  8,  3,  5,  9,  3,  6,  5,  3, 10,  6,  3,  1, 10,  3, -1],
+    [ 5,  9,  0,  6,  5,  0, 10,  6,  0,  1, 10,  0, -1, -1, -1, -1],
+    [ 8,  3,  0, 10,  6,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  6,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11, 10,  5,  7, 11,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 11, 10,  5,  7, 11,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0, 11, 10,  5,  7, 11,  5, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  1,  3,  8,  1, 11, 10,  5,  7, 11,  5, -1, -1, -1, -1],
+    [11,  2,  1,  7, 11,  1,  5,  7,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 11,  2,  1,  7, 11,  1,  5,  7,  1, -1, -1, -1, -1],
+    [11,  2,  0,  7, 11,  0,  5,  7,  0,  9,  5,  0, -1, -1, -1, -1],
+    [ 7, 11,  2,  5,  7,  2,  9,  5,  2,  8,  9,  2,  3,  8,  2, -1],
+    [ 7,  3,  2,  5,  7,  2, 10,  5,  2, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  8,  0,  5,  7,  0, 10,  5,  0,  2, 10,  0, -1, -1, -1, -1],
+    [ 9,  1,  0,  7,  3,  2,  5,  7,  2, 10,  5,  2, -1, -1, -1, -1],
+    [ 5,  7,  8, 10,  5,  8,  2, 10,  8,  1,  2,  8,  9,  1,  8, -1],
+    [ 7,  3,  1,  5,  7,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  8,  0,  5,  7,  0,  1,  5,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  3,  0,  5,  7,  0,  9,  5,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  9,  5,  7,  8,  5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  5,  4, 11, 10,  4,  8, 11,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  4,  0, 10,  5,  0, 11, 10,  0,  3, 11,  0, -1, -1, -1, -1],
+    [ 9,  1,  0, 10,  5,  4, 11, 10,  4,  8, 11,  4, -1, -1, -1, -1],
+    [10,  5,  4, 11, 10,  4,  3, 11,  4,  1,  3,  4,  9,  1,  4, -1],
+    [11,  2,  1,  8, 11,  1,  4,  8,  1,  5,  4,  1, -1, -1, -1, -1],
+    [ 1,  5,  4,  2,  1,  4, 11,  2,  4,  3, 11,  4,  0,  3,  4, -1],
+    [ 8, 11,  2,  4,  8,  2,  5,  4,  2,  9,  5,  2,  0,  9,  2, -1],
+    [ 3, 11,  2,  9,  5,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  3,  2,  4,  8,  2,  5,  4,  2, 10,  5,  2, -1, -1, -1, -1],
+    [ 5,  4,  0, 10,  5,  0,  2, 10,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  1,  0,  8,  3,  2,  4,  8,  2,  5,  4,  2, 10,  5,  2, -1],
+    [10,  5,  4,  2, 10,  4,  1,  2,  4,  9,  1,  4, -1, -1, -1, -1],
+    [ 8,  3,  1,  4,  8,  1,  5,  4,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 5,  4,  0,  1,  5,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 4,  8,  3,  5,  4,  3,  9,  5,  3,  0,  9,  3, -1, -1, -1, -1],
+    [ 9,  5,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  9,  4, 11, 10,  4,  7, 11,  4, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  8,  0, 10,  9,  4, 11, 10,  4,  7, 11,  4, -1, -1, -1, -1],
+    [10,  1,  0, 11, 10,  0,  7, 11,  0,  4,  7,  0, -1, -1, -1, -1],
+    [11, 10,  1,  7, 11,  1,  4,  7,  1,  8,  4,  1,  3,  8,  1, -1],
+    [11,  2,  1,  7, 11,  1,  4,  7,  1,  9,  4,  1, -1, -1, -1, -1],
+    [ 3,  8,  0, 11,  2,  1,  7, 11,  1,  4,  7,  1,  9,  4,  1, -1],
+    [11,  2,  0,  7, 11,  0,  4,  7,  0, -1, -1, -1, -1, -1, -1, -1],
+    [ 7, 11,  2,  4,  7,  2,  8,  4,  2,  3,  8,  2, -1, -1, -1, -1],
+    [ 7,  3,  2,  4,  7,  2,  9,  4,  2, 10,  9,  2, -1, -1, -1, -1],
+    [ 9,  4,  7, 10,  9,  7,  2, 10,  7,  0,  2,  7,  8,  0,  7, -1],
+    [ 3,  2, 10,  7,  3, 10,  4,  7, 10,  0,  4, 10,  1,  0, 10, -1],
+    [ 2, 10,  1,  7,  8,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  3,  1,  4,  7,  1,  9,  4,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 9,  4,  7,  1,  9,  7,  0,  1,  7,  8,  0,  7, -1, -1, -1, -1],
+    [ 7,  3,  0,  4,  7,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 7,  8,  4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  9,  8, 11, 10,  8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10,  9,  0, 11, 10,  0,  3, 11,  0, -1, -1, -1, -1, -1, -1, -1],
+    [10,  1,  0, 11, 10,  0,  8, 11,  0, -1, -1, -1, -1, -1, -1, -1],
+    [11, 10,  1,  3, 11,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11,  2,  1,  8, 11,  1,  9,  8,  1, -1, -1, -1, -1, -1, -1, -1],
+    [ 2,  1,  9, 11,  2,  9,  3, 11,  9,  0,  3,  9, -1, -1, -1, -1],
+    [11,  2,  0,  8, 11,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3, 11,  2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  3,  2,  9,  8,  2, 10,  9,  2, -1, -1, -1, -1, -1, -1, -1],
+    [10,  9,  0,  2, 10,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 3,  2, 10,  8,  3, 10,  0,  8, 10,  1,  0, 10, -1, -1, -1, -1],
+    [ 2, 10,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  3,  1,  9,  8,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 1,  9,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 8,  3,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1]];
+
+/// Represents the surface where a scalar field crosses an iso-level,
+/// found by marching cubes over a regular grid of samples.
+///
+/// Points where the field is below the iso-level are inside the surface,
+/// so a signed distance function gives a surface with the triangles
+/// facing out. Vertices on the edges of the grid are shared by every
+/// triangle that touches them. Where the surface leaves the bounds it is
+/// left open.
+#[derive(Clone)]
+pub struct MarchingCubes {
+    range: Range<usize>,
+    vertices: Vec<Vec3>,
+    faces: Vec<Triangle<usize>>
+}
+
+impl MarchingCubes {
+    /// Create a new surface.
+    /// `field` is sampled at every point of the grid.
+    /// `min` and `max` are the opposite corners of the grid.
+    /// `resolution` is the number of cells along the x, y and z axes.
+    /// `iso` is the value of the field on the surface.
+    pub fn new<F>(field: F,
+                  min: Vec3,
+                  max: Vec3,
+                  resolution: (usize, usize, usize),
+                  iso: f32) -> MarchingCubes
+        where F: Fn(f32, f32, f32) -> f32 {
+
+        let (nx, ny, nz) = resolution;
+        assert!(nx > 0 && ny > 0 && nz > 0);
+
+        let step = ((max.0 - min.0) / nx as f32,
+                    (max.1 - min.1) / ny as f32,
+                    (max.2 - min.2) / nz as f32);
+        let point = |(i, j, k): (usize, usize, usize)| {
+            (min.0 + step.0 * i as f32,
+             min.1 + step.1 * j as f32,
+             min.2 + step.2 * k as f32)
+        };
+        let lattice = |(i, j, k): (usize, usize, usize)| {
+            (k * (ny + 1) + j) * (nx + 1) + i
+        };
+
+        let mut samples = Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1));
+        for k in 0..nz + 1 {
+            for j in 0..ny + 1 {
+                for i in 0..nx + 1 {
+                    let (x, y, z) = point((i, j, k));
+                    samples.push(field(x, y, z));
+                }
+            }
+        }
+
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        let mut welded = HashMap::new();
+
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let corner = |c: usize| {
+                        let (dx, dy, dz) = CORNERS[c];
+                        (i + dx, j + dy, k + dz)
+                    };
+
+                    let mut case = 0;
+                    for c in 0..8 {
+                        if samples[lattice(corner(c))] < iso {
+                            case |= 1 << c;
+                        }
+                    }
+
+                    let mut vertex = |e: i8| {
+                        let (a, b) = EDGES[e as usize];
+                        let (a, b) = (corner(a), corner(b));
+                        let (a, b) = if lattice(a) < lattice(b) { (a, b) } else { (b, a) };
+                        let key = (lattice(a), lattice(b));
+                        if let Some(&idx) = welded.get(&key) {
+                            return idx;
+                        }
+
+                        let (fa, fb) = (samples[key.0], samples[key.1]);
+                        let t = (iso - fa) / (fb - fa);
+                        let idx = vertices.len();
+                        vertices.push(add(point(a), scale(sub(point(b), point(a)), t)));
+                        welded.insert(key, idx);
+                        idx
+                    };
+
+                    let edges = &TRIANGLES[case];
+                    let mut n = 0;
+                    while edges[n] != -1 {
+                        let x = vertex(edges[n]);
+                        let y = vertex(edges[n + 1]);
+                        let z = vertex(edges[n + 2]);
+                        faces.push(Triangle::new(x, y, z));
+                        n += 3;
+                    }
+                }
+            }
+        }
+
+        MarchingCubes {
+            range: 0..faces.len(),
+            vertices: vertices,
+            faces: faces
+        }
+    }
+}
+
+impl Iterator for MarchingCubes {
+    type Item = Triangle<Vec3>;
+
+    fn next(&mut self) -> Option<Triangle<Vec3>> {
+        self.range.next().map(|idx| {
+            self.faces[idx].map_vertex(|i| self.vertices[i])
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for MarchingCubes {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Triangle<usize>> for MarchingCubes {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        self.faces[idx]
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+}
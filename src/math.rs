@@ -0,0 +1,64 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+//! small helpers for working with `(f32, f32, f32)` vectors
+
+use std::num::Float;
+
+pub type Vec3 = (f32, f32, f32);
+
+pub fn add(a: Vec3, b: Vec3) -> Vec3 {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+pub fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+pub fn scale(a: Vec3, s: f32) -> Vec3 {
+    (a.0 * s, a.1 * s, a.2 * s)
+}
+
+pub fn dot(a: Vec3, b: Vec3) -> f32 {
+    a.0 * b.0 + a.1 * b.1 + a.2 * b.2
+}
+
+pub fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    (a.1 * b.2 - a.2 * b.1,
+     a.2 * b.0 - a.0 * b.2,
+     a.0 * b.1 - a.1 * b.0)
+}
+
+pub fn length(a: Vec3) -> f32 {
+    dot(a, a).sqrt()
+}
+
+/// scale `a` to a length of 1
+pub fn normalize(a: Vec3) -> Vec3 {
+    scale(a, 1. / length(a))
+}
+
+/// raise the magnitude of `x` to the power `e` keeping the sign of `x`,
+/// values within 1e-6 of 0 are treated as 0 so that the rounding error of
+/// `sin` and `cos` near multiples of π/2 does not push points off of the
+/// axis planes
+pub fn spow(x: f32, e: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        0.
+    } else if x < 0. {
+        -(-x).powf(e)
+    } else {
+        x.powf(e)
+    }
+}
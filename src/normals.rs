@@ -0,0 +1,147 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use super::{MapVertex, EmitTriangles, Triangle};
+use super::generators::{SharedVertex, IndexedPolygon};
+use indexed::Indexed;
+use math::{Vec3, add, sub, cross, length, normalize};
+
+/// A vertex with a position and a normal
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub struct Vertex {
+    /// the position of the vertex
+    pub pos: (f32, f32, f32),
+    /// the normal of the vertex, with a length of 1
+    pub normal: (f32, f32, f32)
+}
+
+/// the normal of a polygon, weighted by its area
+fn polygon_normal<P>(polygon: &P, pos: &[Vec3]) -> Vec3
+    where P: EmitTriangles<Vertex=usize> {
+
+    let mut normal = (0., 0., 0.);
+    polygon.emit_triangles(|Triangle{x, y, z}| {
+        normal = add(normal, cross(sub(pos[y], pos[x]), sub(pos[z], pos[x])));
+    });
+    normal
+}
+
+/// scale `n` to a length of 1, leaving it alone if it has no direction
+fn unit(n: Vec3) -> Vec3 {
+    if length(n) > 0. { normalize(n) } else { n }
+}
+
+/// build a `Normals` from vertices that already have their normals
+pub fn from_parts<P>(vertices: Vec<Vertex>, polygons: Vec<P>) -> Normals<P> {
+    Normals { indexed: Indexed::new(vertices, polygons) }
+}
+
+/// Adds a normal to each vertex of another generator.
+///
+/// The normals can be flat, where each polygon has its own vertices that
+/// all share the normal of the polygon, or smooth, where the vertices are
+/// shared like in the original generator. The polygons face the same way as
+/// the polygons of the original generator.
+#[derive(Clone)]
+pub struct Normals<P> {
+    indexed: Indexed<Vertex, P>
+}
+
+impl<P: Clone> Normals<P> {
+    /// Give each polygon of `generator` its own vertices, with the normal
+    /// of the polygon. This is what a cube or any other faceted shape needs.
+    pub fn flat<G>(generator: &G) -> Normals<P>
+        where G: SharedVertex<Vec3> + IndexedPolygon<P>,
+              P: EmitTriangles<Vertex=usize> + MapVertex<usize, usize, Output=P> {
+
+        let pos: Vec<Vec3> = generator.shared_vertex_iter().collect();
+        let mut vertices = Vec::new();
+        let mut polygons = Vec::with_capacity(generator.indexed_polygon_count());
+
+        for polygon in generator.indexed_polygon_iter() {
+            let normal = unit(polygon_normal(&polygon, &pos));
+            polygons.push(polygon.map_vertex(|i| {
+                vertices.push(Vertex { pos: pos[i], normal: normal });
+                vertices.len() - 1
+            }));
+        }
+        from_parts(vertices, polygons)
+    }
+
+    /// Keep the shared vertices of `generator`, each normal is the average
+    /// of the normals of the polygons around the vertex, weighted by their area.
+    pub fn smooth<G>(generator: &G) -> Normals<P>
+        where G: SharedVertex<Vec3> + IndexedPolygon<P>,
+              P: EmitTriangles<Vertex=usize> {
+
+        let pos: Vec<Vec3> = generator.shared_vertex_iter().collect();
+        let mut normals = vec![(0., 0., 0.); pos.len()];
+        let polygons: Vec<P> = generator.indexed_polygon_iter().collect();
+
+        for polygon in polygons.iter() {
+            polygon.emit_triangles(|Triangle{x, y, z}| {
+                let n = cross(sub(pos[y], pos[x]), sub(pos[z], pos[x]));
+                for &i in [x, y, z].iter() {
+                    normals[i] = add(normals[i], n);
+                }
+            });
+        }
+
+        let vertices = pos.iter().zip(normals.iter()).map(|(&pos, &n)| {
+            Vertex { pos: pos, normal: unit(n) }
+        }).collect();
+        from_parts(vertices, polygons)
+    }
+
+    /// Keep the shared vertices of `generator`, each normal is found
+    /// from the position of the vertex by `f`. This is for surfaces
+    /// where the normal is known, like a sphere.
+    pub fn analytic<G, F>(generator: &G, f: F) -> Normals<P>
+        where G: SharedVertex<Vec3> + IndexedPolygon<P>,
+              F: Fn(Vec3) -> Vec3 {
+
+        let vertices = generator.shared_vertex_iter().map(|pos| {
+            Vertex { pos: pos, normal: unit(f(pos)) }
+        }).collect();
+        from_parts(vertices, generator.indexed_polygon_iter().collect())
+    }
+}
+
+impl<P> Iterator for Normals<P> where P: Clone + MapVertex<usize, Vertex> {
+    type Item = <P as MapVertex<usize, Vertex>>::Output;
+
+    fn next(&mut self) -> Option<<P as MapVertex<usize, Vertex>>::Output> {
+        self.indexed.next()
+    }
+}
+
+impl<P> SharedVertex<Vertex> for Normals<P> {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.indexed.shared_vertex(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.indexed.shared_vertex_count()
+    }
+}
+
+impl<P: Clone> IndexedPolygon<P> for Normals<P> {
+    fn indexed_polygon(&self, idx: usize) -> P {
+        self.indexed.indexed_polygon(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.indexed.indexed_polygon_count()
+    }
+}
@@ -0,0 +1,78 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{MapVertex, Triangle};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+const VERTICES: [(f32, f32, f32); 6] = [
+    ( 1.,  0.,  0.), (-1.,  0.,  0.),
+    ( 0.,  1.,  0.), ( 0., -1.,  0.),
+    ( 0.,  0.,  1.), ( 0.,  0., -1.)
+];
+
+const FACES: [[usize; 3]; 8] = [
+    [3, 1, 5], [4, 1, 3], [5, 1, 2], [2, 1, 4],
+    [5, 0, 3], [3, 0, 4], [2, 0, 5], [4, 0, 2]
+];
+
+/// A regular octahedron, centered at (0, 0, 0) with each vertex 1 away from the origin
+#[derive(Clone)]
+pub struct Octahedron {
+    range: Range<usize>
+}
+
+impl Octahedron {
+    /// create a new octahedron generator
+    pub fn new() -> Octahedron {
+        Octahedron { range: 0..8 }
+    }
+
+    fn vert(&self, idx: usize) -> (f32, f32, f32) {
+        VERTICES[idx]
+    }
+
+    fn face_indexed(&self, idx: usize) -> Triangle<usize> {
+        let f = FACES[idx];
+        Triangle::new(f[0], f[1], f[2])
+    }
+
+    fn face(&self, idx: usize) -> Triangle<(f32, f32, f32)> {
+        self.face_indexed(idx).map_vertex(|i| self.vert(i))
+    }
+}
+
+impl Iterator for Octahedron {
+    type Item = Triangle<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Triangle<(f32, f32, f32)>> {
+        self.range.next().map(|idx| self.face(idx))
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Octahedron {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        self.vert(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize { 6 }
+}
+
+impl IndexedPolygon<Triangle<usize>> for Octahedron {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        self.face_indexed(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize { 8 }
+}
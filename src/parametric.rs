@@ -0,0 +1,111 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{MapVertex, Quad};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a surface defined by a function `f(u, v)` that maps each point
+/// of the domain 0 to 1 in both `u` and `v` to a position.
+#[derive(Clone)]
+pub struct ParametricSurface<F> {
+    range: Range<usize>,
+    sub_u: usize,
+    sub_v: usize,
+    wrap_u: bool,
+    wrap_v: bool,
+    f: F
+}
+
+impl<F: Fn(f32, f32) -> (f32, f32, f32)> ParametricSurface<F> {
+    /// Create a new parametric surface, this is a grid of quads like a
+    /// subdivided `Plane`. Each face is wound counter-clockwise when
+    /// looking against the direction of `df/du x df/dv`.
+    /// `u` is the number of subdivisions in the u axis
+    /// `v` is the number of subdivisions in the v axis
+    /// `f` is the function mapping (u, v) to a position
+    pub fn new(u: usize, v: usize, f: F) -> ParametricSurface<F> {
+        assert!(u > 0 && v > 0);
+        ParametricSurface {
+            range: 0..u * v,
+            sub_u: u,
+            sub_v: v,
+            wrap_u: false,
+            wrap_v: false,
+            f: f
+        }
+    }
+
+    /// Mark the surface as wrapping in `u` and/or `v`. A wrapped axis
+    /// requires that `f` returns the same point at 0 and 1, the vertices
+    /// along that seam are then shared instead of duplicated.
+    pub fn wrap(mut self, u: bool, v: bool) -> ParametricSurface<F> {
+        assert!(!u || self.sub_u > 2);
+        assert!(!v || self.sub_v > 2);
+        self.wrap_u = u;
+        self.wrap_v = v;
+        self
+    }
+
+    fn columns(&self) -> usize {
+        if self.wrap_u { self.sub_u } else { self.sub_u + 1 }
+    }
+
+    fn rows(&self) -> usize {
+        if self.wrap_v { self.sub_v } else { self.sub_v + 1 }
+    }
+
+    fn index(&self, u: usize, v: usize) -> usize {
+        (v % self.rows()) * self.columns() + (u % self.columns())
+    }
+}
+
+impl<F: Fn(f32, f32) -> (f32, f32, f32)> Iterator for ParametricSurface<F> {
+    type Item = Quad<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Quad<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl<F: Fn(f32, f32) -> (f32, f32, f32)> SharedVertex<(f32, f32, f32)> for ParametricSurface<F> {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        let u = idx % self.columns();
+        let v = idx / self.columns();
+        (self.f)(u as f32 / self.sub_u as f32,
+                 v as f32 / self.sub_v as f32)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.columns() * self.rows()
+    }
+}
+
+impl<F: Fn(f32, f32) -> (f32, f32, f32)> IndexedPolygon<Quad<usize>> for ParametricSurface<F> {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let u = idx % self.sub_u;
+        let v = idx / self.sub_u;
+
+        Quad::new(self.index(u,   v),
+                  self.index(u+1, v),
+                  self.index(u+1, v+1),
+                  self.index(u,   v+1))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_u * self.sub_v
+    }
+}
@@ -14,9 +14,12 @@
 
 use super::Quad;
 use super::generators::{SharedVertex, IndexedPolygon};
+use super::Vertex;
+use normals::{Normals, from_parts};
+use textured::{self, Textured, TexturedVertex};
 
 /// Represents a 2D plane with origin of (0, 0), from 1 to -1
-#[derive(Copy)]
+#[derive(Clone, Copy)]
 pub struct Plane {
     subdivide_x: usize,
     subdivide_y: usize,
@@ -49,6 +52,28 @@ impl Plane {
         }
     }
 
+    /// Create a generator with the plane lying at z = 0, the normals
+    /// point along the z axis.
+    pub fn normals(&self) -> Normals<Quad<usize>> {
+        let vertices = self.shared_vertex_iter().map(|(x, y)| {
+            Vertex { pos: (x, y, 0.), normal: (0., 0., 1.) }
+        }).collect();
+        from_parts(vertices, self.indexed_polygon_iter().collect())
+    }
+
+    /// Create a generator with texture coordinates, the texture covers the
+    /// plane from (0, 0) at (-1, -1) to (1, 1) at (1, 1). The plane lies
+    /// at z = 0 like the one made by `normals`.
+    pub fn textured(&self) -> Textured<Quad<usize>> {
+        let vertices = (0..self.shared_vertex_count()).map(|idx| {
+            let (x, y) = self.shared_vertex(idx);
+            let u = (idx % (self.subdivide_x + 1)) as f32 / self.subdivide_x as f32;
+            let v = (idx / (self.subdivide_x + 1)) as f32 / self.subdivide_y as f32;
+            TexturedVertex { pos: (x, y, 0.), normal: (0., 0., 1.), uv: (u, v) }
+        }).collect();
+        textured::from_parts(vertices, self.indexed_polygon_iter().collect())
+    }
+
     fn vert(&self, x: usize, y: usize) -> (f32, f32) {
         let sx = self.subdivide_x as f32;
         let sy = self.subdivide_y as f32;
@@ -62,6 +62,65 @@ impl<T> Triangle<T> {
     }
 }
 
+/// A polygon with 5 points.
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub struct Pentagon<T> {
+    /// the first point of a pentagon
+    pub x: T,
+    /// the second point of a pentagon
+    pub y: T,
+    /// the third point of a pentagon
+    pub z: T,
+    /// the fourth point of a pentagon
+    pub w: T,
+    /// the fifth point of a pentagon
+    pub v: T,
+}
+
+impl<T> Pentagon<T> {
+    /// create a new `Pentagon` with supplied vertices
+    pub fn new(v0: T, v1: T, v2: T, v3: T, v4: T) -> Pentagon<T> {
+        Pentagon {
+            x: v0,
+            y: v1,
+            z: v2,
+            w: v3,
+            v: v4
+        }
+    }
+}
+
+/// A polygon with 6 points.
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub struct Hexagon<T> {
+    /// the first point of a hexagon
+    pub x: T,
+    /// the second point of a hexagon
+    pub y: T,
+    /// the third point of a hexagon
+    pub z: T,
+    /// the fourth point of a hexagon
+    pub w: T,
+    /// the fifth point of a hexagon
+    pub v: T,
+    /// the sixth point of a hexagon
+    pub u: T,
+}
+
+impl<T> Hexagon<T> {
+    /// create a new `Hexagon` with supplied vertices
+    pub fn new(v0: T, v1: T, v2: T, v3: T, v4: T, v5: T) -> Hexagon<T> {
+        Hexagon {
+            x: v0,
+            y: v1,
+            z: v2,
+            w: v3,
+            v: v4,
+            u: v5
+        }
+    }
+}
+
 /// This is All-the-types container. This exists since some generators
 /// produce both `Triangles` and `Quads`.
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -100,6 +159,29 @@ impl<T> EmitVertices<T> for Quad<T> {
     }
 }
 
+impl<T> EmitVertices<T> for Pentagon<T> {
+    fn emit_vertices<F>(self, mut emit: F) where F: FnMut(T) {
+        let Pentagon{x, y, z, w, v} = self;
+        emit(x);
+        emit(y);
+        emit(z);
+        emit(w);
+        emit(v);
+    }
+}
+
+impl<T> EmitVertices<T> for Hexagon<T> {
+    fn emit_vertices<F>(self, mut emit: F) where F: FnMut(T) {
+        let Hexagon{x, y, z, w, v, u} = self;
+        emit(x);
+        emit(y);
+        emit(z);
+        emit(w);
+        emit(v);
+        emit(u);
+    }
+}
+
 impl<T> EmitVertices<T> for Polygon<T> {
     fn emit_vertices<F>(self, emit: F) where F: FnMut(T) {
         use self::Polygon::{ PolyQuad, PolyTri };
@@ -189,6 +271,37 @@ impl<T: Clone, U> MapVertex<T, U> for Quad<T> {
     }
 }
 
+impl<T: Clone, U> MapVertex<T, U> for Pentagon<T> {
+    type Output = Pentagon<U>;
+
+    fn map_vertex<F>(self, mut map: F) -> Pentagon<U> where F: FnMut(T) -> U {
+        let Pentagon{x, y, z, w, v} = self;
+        Pentagon {
+            x: map(x),
+            y: map(y),
+            z: map(z),
+            w: map(w),
+            v: map(v)
+        }
+    }
+}
+
+impl<T: Clone, U> MapVertex<T, U> for Hexagon<T> {
+    type Output = Hexagon<U>;
+
+    fn map_vertex<F>(self, mut map: F) -> Hexagon<U> where F: FnMut(T) -> U {
+        let Hexagon{x, y, z, w, v, u} = self;
+        Hexagon {
+            x: map(x),
+            y: map(y),
+            z: map(z),
+            w: map(w),
+            v: map(v),
+            u: map(u)
+        }
+    }
+}
+
 impl<T: Clone, U> MapVertex<T, U> for Polygon<T> {
     type Output = Polygon<U>;
 
@@ -0,0 +1,115 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI;
+use std::num::Float;
+use super::{MapVertex, Quad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use cube::Cube;
+use math::{Vec3, add, scale, normalize};
+
+/// Represents a box with rounded edges and corners, centered at (0, 0, 0).
+///
+/// This is built on a subdivided `Cube`, the edges are cylinder patches and
+/// the corners are sphere octants. With a radius of 0 this is exactly `Cube`
+/// scaled by the half extents.
+#[derive(Clone)]
+pub struct RoundedBox {
+    range: Range<usize>,
+    cube: Cube,
+    half_extents: Vec3,
+    radius: f32,
+    segments: usize
+}
+
+impl RoundedBox {
+    /// Create a new rounded box.
+    /// `half_extents` is the distance from the center to each face.
+    /// `radius` is the radius of the rounded edges and corners.
+    /// `segments` is the number of quads each face contributes to a rounded
+    /// edge, each edge is made of `2 * segments` quads.
+    pub fn new(half_extents: Vec3, radius: f32, segments: usize) -> RoundedBox {
+        let (x, y, z) = half_extents;
+        assert!(segments > 0);
+        assert!(radius >= 0. && radius <= x.min(y).min(z));
+
+        let cube = if radius == 0. {
+            Cube::new()
+        } else {
+            let n = 2 * segments + 1;
+            Cube::subdivide(n, n, n)
+        };
+
+        RoundedBox {
+            range: 0..cube.indexed_polygon_count(),
+            cube: cube,
+            half_extents: half_extents,
+            radius: radius,
+            segments: segments
+        }
+    }
+
+    /// find the position of the inner box, and the direction from it, of
+    /// the lattice point `k` along an axis with a half extent of `h`
+    fn axis(&self, k: usize, h: f32) -> (f32, f32) {
+        let inner = h - self.radius;
+        let step = PI * 0.25 / self.segments as f32;
+        if k <= self.segments {
+            (-inner, -(step * (self.segments - k) as f32).tan())
+        } else {
+            (inner, (step * (k - self.segments - 1) as f32).tan())
+        }
+    }
+}
+
+impl Iterator for RoundedBox {
+    type Item = Quad<Vec3>;
+
+    fn next(&mut self) -> Option<Quad<Vec3>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for RoundedBox {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        let (hx, hy, hz) = self.half_extents;
+        if self.radius == 0. {
+            let (x, y, z) = self.cube.shared_vertex(idx);
+            return (x * hx, y * hy, z * hz);
+        }
+
+        let (i, j, k) = self.cube.lattice(idx);
+        let (ix, dx) = self.axis(i, hx);
+        let (iy, dy) = self.axis(j, hy);
+        let (iz, dz) = self.axis(k, hz);
+        add((ix, iy, iz), scale(normalize((dx, dy, dz)), self.radius))
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.cube.shared_vertex_count()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for RoundedBox {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        self.cube.indexed_polygon(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.cube.indexed_polygon_count()
+    }
+}
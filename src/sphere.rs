@@ -17,14 +17,24 @@ use std::num::Float;
 use super::{Quad, Triangle, Polygon};
 use super::Polygon::{PolyTri, PolyQuad};
 use super::generators::{SharedVertex, IndexedPolygon};
+use normals::Normals;
+use textured::{Textured, TexturedVertex, from_parts};
 
 /// Represents a sphere with radius of 1, centered at (0, 0, 0)
+///
+/// `phi` is the angle around the z axis, starting at the x axis, and
+/// `theta` is the angle down from the pole at z = 1. Limiting either range
+/// gives a part of the sphere, such as a dome or a spherical cap.
 #[derive(Copy)]
 pub struct SphereUV {
     u: usize,
     v: usize,
     sub_u: usize,
-    sub_v: usize
+    sub_v: usize,
+    phi_start: f32,
+    phi_length: f32,
+    theta_start: f32,
+    theta_length: f32
 }
 
 impl SphereUV {
@@ -36,18 +46,143 @@ impl SphereUV {
             u: 0,
             v: 0,
             sub_u: u,
-            sub_v: v
+            sub_v: v,
+            phi_start: 0.,
+            phi_length: PI_2,
+            theta_start: 0.,
+            theta_length: PI
         }
     }
 
+    /// Limit the sphere to the angles from `start` to `start + length` around
+    /// the z axis. When the range is not a full circle each ring gets an
+    /// extra vertex, so the two edges of the range are not joined.
+    pub fn phi(mut self, start: f32, length: f32) -> SphereUV {
+        assert!(length > 0. && length <= PI_2);
+        self.phi_start = start;
+        self.phi_length = length;
+        self
+    }
+
+    /// Limit the sphere to the angles from `start` to `start + length` down
+    /// from the pole at z = 1. A pole is only collapsed to a single vertex
+    /// if the range reaches it.
+    pub fn theta(mut self, start: f32, length: f32) -> SphereUV {
+        assert!(start >= 0. && length > 0. && start + length <= PI + 1e-6);
+        self.theta_start = start;
+        self.theta_length = length;
+        self
+    }
+
+    /// Create a generator with smooth normals, the normal of each
+    /// vertex points away from the center of the sphere.
+    pub fn normals(&self) -> Normals<Polygon<usize>> {
+        Normals::analytic(self, |p| p)
+    }
+
+    /// Create a generator with equirectangular texture coordinates, u
+    /// runs from 0 to 1 around the z axis and v from 1 at the pole at
+    /// z = 1 to 0 at the pole at z = -1. A part of the sphere gets the
+    /// matching part of the texture.
+    ///
+    /// The first and last column of each ring are separate vertices so the
+    /// seam can have both a u of 0 and 1, and a pole is split so that
+    /// each triangle touching it gets the u of the middle of its column.
+    pub fn textured(&self) -> Textured<Polygon<usize>> {
+        let mut vertices = Vec::new();
+        let mut rings = Vec::with_capacity(self.sub_v + 1);
+
+        for v in 0..self.sub_v + 1 {
+            let pole = (v == 0 && self.top_pole()) ||
+                       (v == self.sub_v && self.bottom_pole());
+            let (count, offset) = if pole { (self.sub_u, 0.5) } else { (self.sub_u + 1, 0.) };
+
+            rings.push(vertices.len());
+            for u in 0..count {
+                let pos = self.vert(if pole { 0 } else { u }, v);
+                vertices.push(TexturedVertex {
+                    pos: pos,
+                    normal: pos,
+                    uv: self.uv(u as f32 + offset, v)
+                });
+            }
+        }
+
+        let index = |u: usize, v: usize| rings[v] + u;
+        let polygons = (0..self.indexed_polygon_count()).map(|idx| {
+            let u = idx % self.sub_u;
+            let v = idx / self.sub_u;
+
+            if v == 0 && self.top_pole() {
+                PolyTri(Triangle::new(index(u,   v),
+                                      index(u,   v+1),
+                                      index(u+1, v+1)))
+            } else if self.sub_v - 1 == v && self.bottom_pole() {
+                PolyTri(Triangle::new(index(u,   v+1),
+                                      index(u+1, v),
+                                      index(u,   v)))
+            } else {
+                PolyQuad(Quad::new(index(u,   v),
+                                   index(u,   v+1),
+                                   index(u+1, v+1),
+                                   index(u+1, v)))
+            }
+        }).collect();
+        from_parts(vertices, polygons)
+    }
+
+    fn uv(&self, u: f32, v: usize) -> (f32, f32) {
+        let phi = self.phi_start + (u / self.sub_u as f32) * self.phi_length;
+        let theta = self.theta_start + (v as f32 / self.sub_v as f32) * self.theta_length;
+        (phi / PI_2, 1. - theta / PI)
+    }
+
     fn vert(&self, u: usize, v: usize) -> (f32, f32, f32) {
-        let u = (u as f32 / self.sub_u as f32) * PI_2;
-        let v = (v as f32 / self.sub_v as f32) * PI;
+        let u = self.phi_start + (u as f32 / self.sub_u as f32) * self.phi_length;
+        let v = self.theta_start + (v as f32 / self.sub_v as f32) * self.theta_length;
 
         (u.cos() * v.sin(),
          u.sin() * v.sin(),
          v.cos())
     }
+
+    /// does the range go all the way around the z axis
+    fn wraps(&self) -> bool {
+        self.phi_length >= PI_2
+    }
+
+    fn top_pole(&self) -> bool {
+        self.theta_start == 0.
+    }
+
+    fn bottom_pole(&self) -> bool {
+        self.theta_start + self.theta_length >= PI - 1e-6
+    }
+
+    /// number of vertices in each ring
+    fn ring_size(&self) -> usize {
+        if self.wraps() { self.sub_u } else { self.sub_u + 1 }
+    }
+
+    /// number of rings that are not collapsed to a pole
+    fn ring_count(&self) -> usize {
+        let mut count = self.sub_v + 1;
+        if self.top_pole() { count -= 1; }
+        if self.bottom_pole() { count -= 1; }
+        count
+    }
+
+    fn index(&self, u: usize, v: usize) -> usize {
+        let top = if self.top_pole() { 1 } else { 0 };
+        if v == 0 && self.top_pole() {
+            0
+        } else if v == self.sub_v && self.bottom_pole() {
+            self.shared_vertex_count() - 1
+        } else {
+            let u = if self.wraps() { u % self.sub_u } else { u };
+            top + (v - top) * self.ring_size() + u
+        }
+    }
 }
 
 impl Iterator for SphereUV {
@@ -69,9 +204,9 @@ impl Iterator for SphereUV {
         let v = self.v;
         self.u += 1;
 
-        if v == 0 {
+        if v == 0 && self.top_pole() {
             Some(PolyTri(Triangle::new(x, y, z)))
-        } else if v == self.sub_v - 1 {
+        } else if v == self.sub_v - 1 && self.bottom_pole() {
             Some(PolyTri(Triangle::new(z, w, x)))
         } else {
             Some(PolyQuad(Quad::new(x, y, z, w)))
@@ -81,22 +216,25 @@ impl Iterator for SphereUV {
 
 impl SharedVertex<(f32, f32, f32)> for SphereUV {
     fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
-        if idx == 0 {
+        let top = if self.top_pole() { 1 } else { 0 };
+        if idx == 0 && self.top_pole() {
             self.vert(0, 0)
-        } else if idx == self.shared_vertex_count() - 1 {
+        } else if idx == self.shared_vertex_count() - 1 && self.bottom_pole() {
             self.vert(0, self.sub_v)
         } else {
             // since the bottom verts all map to the same
             // we jump over them in index space
-            let idx = idx - 1;
-            let u = idx % (self.sub_u);
-            let v = idx / (self.sub_u);
-            self.vert(u, v+1)
+            let idx = idx - top;
+            let u = idx % self.ring_size();
+            let v = idx / self.ring_size();
+            self.vert(u, v + top)
         }
     }
 
     fn shared_vertex_count(&self) -> usize {
-        (self.sub_v - 1) * (self.sub_u) + 2
+        self.ring_count() * self.ring_size() +
+            if self.top_pole() { 1 } else { 0 } +
+            if self.bottom_pole() { 1 } else { 0 }
     }
 }
 
@@ -105,29 +243,19 @@ impl IndexedPolygon<Polygon<usize>> for SphereUV {
         let u = idx % self.sub_u;
         let v = idx / self.sub_u;
 
-        let f = |u: usize, v: usize| {
-            if v == 0 {
-                0
-            } else if self.sub_v == v {
-                (self.sub_v-1) * (self.sub_u) + 1
-            } else {
-                (v-1) * self.sub_u + (u % self.sub_u) + 1
-            }
-        };
-
-        if v == 0 {
-            PolyTri(Triangle::new(f(u,   v),
-                                  f(u,   v+1),
-                                  f(u+1, v+1)))
-        } else if self.sub_v - 1 == v {
-            PolyTri(Triangle::new(f(u+1, v+1),
-                                  f(u+1, v),
-                                  f(u,   v)))
+        if v == 0 && self.top_pole() {
+            PolyTri(Triangle::new(self.index(u,   v),
+                                  self.index(u,   v+1),
+                                  self.index(u+1, v+1)))
+        } else if self.sub_v - 1 == v && self.bottom_pole() {
+            PolyTri(Triangle::new(self.index(u+1, v+1),
+                                  self.index(u+1, v),
+                                  self.index(u,   v)))
         } else {
-            PolyQuad(Quad::new(f(u,   v),
-                               f(u,   v+1),
-                               f(u+1, v+1),
-                               f(u+1, v)))
+            PolyQuad(Quad::new(self.index(u,   v),
+                               self.index(u,   v+1),
+                               self.index(u+1, v+1),
+                               self.index(u+1, v)))
         }
     }
 
@@ -135,4 +263,3 @@ impl IndexedPolygon<Polygon<usize>> for SphereUV {
         self.sub_v * self.sub_u
     }
 }
-
@@ -0,0 +1,145 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::{PI, PI_2};
+use std::num::Float;
+use super::{Quad, Triangle, Polygon, MapVertex};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use math::spow;
+
+/// Represents a superellipsoid centered at (0, 0, 0), with the poles on
+/// the z axis. With both exponents at 1 this is a sphere with radius of 1,
+/// as they approach 0 it becomes a cube and at 2 it is an octahedron.
+/// The vertices and polygons are laid out the same way as `SphereUV`.
+#[derive(Clone)]
+pub struct Superellipsoid {
+    range: Range<usize>,
+    e1: f32,
+    e2: f32,
+    scale: (f32, f32, f32),
+    sub_u: usize,
+    sub_v: usize
+}
+
+impl Superellipsoid {
+    /// Create a new superellipsoid.
+    /// `e1` is the exponent from pole to pole, `e2` the exponent around
+    /// the z axis. An `e1` near 0 with an `e2` of 1 gives a cylinder.
+    /// `u` is the number of points across the equator.
+    /// `v` is the number of points from pole to pole.
+    pub fn new(e1: f32, e2: f32, u: usize, v: usize) -> Superellipsoid {
+        assert!(e1 > 0. && e2 > 0.);
+        assert!(u > 1 && v > 1);
+        Superellipsoid {
+            range: 0..u * v,
+            e1: e1,
+            e2: e2,
+            scale: (1., 1., 1.),
+            sub_u: u,
+            sub_v: v
+        }
+    }
+
+    /// Scale the superellipsoid by `x`, `y` and `z` along each axis.
+    pub fn scale(mut self, x: f32, y: f32, z: f32) -> Superellipsoid {
+        self.scale = (x, y, z);
+        self
+    }
+
+    fn vert(&self, u: usize, v: usize) -> (f32, f32, f32) {
+        let (sx, sy, sz) = self.scale;
+
+        // the poles are exact, `sin(PI)` is not quite 0
+        if v == 0 {
+            return (0., 0., sz);
+        } else if v == self.sub_v {
+            return (0., 0., -sz);
+        }
+
+        let u = (u as f32 / self.sub_u as f32) * PI_2;
+        let v = (v as f32 / self.sub_v as f32) * PI;
+        let r = spow(v.sin(), self.e1);
+
+        (sx * r * spow(u.cos(), self.e2),
+         sy * r * spow(u.sin(), self.e2),
+         sz * spow(v.cos(), self.e1))
+    }
+
+    fn index(&self, u: usize, v: usize) -> usize {
+        if v == 0 {
+            0
+        } else if v == self.sub_v {
+            (self.sub_v - 1) * self.sub_u + 1
+        } else {
+            (v - 1) * self.sub_u + (u % self.sub_u) + 1
+        }
+    }
+}
+
+impl Iterator for Superellipsoid {
+    type Item = Polygon<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Polygon<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Superellipsoid {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        if idx == 0 {
+            self.vert(0, 0)
+        } else if idx == self.shared_vertex_count() - 1 {
+            self.vert(0, self.sub_v)
+        } else {
+            // since the bottom verts all map to the same
+            // we jump over them in index space
+            let idx = idx - 1;
+            self.vert(idx % self.sub_u, idx / self.sub_u + 1)
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        (self.sub_v - 1) * self.sub_u + 2
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Superellipsoid {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let u = idx % self.sub_u;
+        let v = idx / self.sub_u;
+
+        if v == 0 {
+            PolyTri(Triangle::new(self.index(u,   v),
+                                  self.index(u,   v+1),
+                                  self.index(u+1, v+1)))
+        } else if self.sub_v - 1 == v {
+            PolyTri(Triangle::new(self.index(u+1, v+1),
+                                  self.index(u+1, v),
+                                  self.index(u,   v)))
+        } else {
+            PolyQuad(Quad::new(self.index(u,   v),
+                               self.index(u,   v+1),
+                               self.index(u+1, v+1),
+                               self.index(u+1, v)))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_v * self.sub_u
+    }
+}
@@ -0,0 +1,123 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{Quad, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+use math::spow;
+
+/// Represents a supertoroid centered at (0, 0, 0) lying in the xy plane,
+/// the z axis passes through the hole. With both exponents at 1 this is a
+/// `Torus`, as they approach 0 the tube and the ring become square.
+/// The vertices and polygons are laid out the same way as `Torus`.
+#[derive(Clone)]
+pub struct Supertoroid {
+    range: Range<usize>,
+    radius: f32,
+    tubular_radius: f32,
+    e1: f32,
+    e2: f32,
+    scale: (f32, f32, f32),
+    radial_segments: usize,
+    tubular_segments: usize
+}
+
+impl Supertoroid {
+    /// Create a new supertoroid.
+    /// `radius` is the distance from the center of the supertoroid to the center of the tube.
+    /// `tubular_radius` is the radius of the tube.
+    /// `e1` is the exponent around the tube, `e2` the exponent around the z axis.
+    /// `radial_segments` is the number of segments around the z axis.
+    /// `tubular_segments` is the number of segments around the tube.
+    pub fn new(radius: f32,
+               tubular_radius: f32,
+               e1: f32,
+               e2: f32,
+               radial_segments: usize,
+               tubular_segments: usize) -> Supertoroid {
+        assert!(e1 > 0. && e2 > 0.);
+        assert!(radial_segments > 2 && tubular_segments > 2);
+        Supertoroid {
+            range: 0..radial_segments * tubular_segments,
+            radius: radius,
+            tubular_radius: tubular_radius,
+            e1: e1,
+            e2: e2,
+            scale: (1., 1., 1.),
+            radial_segments: radial_segments,
+            tubular_segments: tubular_segments
+        }
+    }
+
+    /// Scale the supertoroid by `x`, `y` and `z` along each axis.
+    pub fn scale(mut self, x: f32, y: f32, z: f32) -> Supertoroid {
+        self.scale = (x, y, z);
+        self
+    }
+
+    fn vert(&self, u: usize, v: usize) -> (f32, f32, f32) {
+        let (sx, sy, sz) = self.scale;
+        let u = (u as f32 / self.radial_segments as f32) * PI_2;
+        let v = (v as f32 / self.tubular_segments as f32) * PI_2;
+        let r = self.radius + self.tubular_radius * spow(v.cos(), self.e1);
+
+        (sx * r * spow(u.cos(), self.e2),
+         sy * r * spow(u.sin(), self.e2),
+         sz * self.tubular_radius * spow(v.sin(), self.e1))
+    }
+
+    fn index(&self, u: usize, v: usize) -> usize {
+        (v % self.tubular_segments) * self.radial_segments + (u % self.radial_segments)
+    }
+}
+
+impl Iterator for Supertoroid {
+    type Item = Quad<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Quad<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Supertoroid {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        let u = idx % self.radial_segments;
+        let v = idx / self.radial_segments;
+        self.vert(u, v)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.radial_segments * self.tubular_segments
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for Supertoroid {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let u = idx % self.radial_segments;
+        let v = idx / self.radial_segments;
+
+        Quad::new(self.index(u,   v),
+                  self.index(u+1, v),
+                  self.index(u+1, v+1),
+                  self.index(u,   v+1))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.radial_segments * self.tubular_segments
+    }
+}
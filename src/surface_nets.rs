@@ -0,0 +1,93 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{Quad, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+use contour::Grid;
+use math::{Vec3, add, scale};
+
+/// Represents the surface where a scalar field crosses an iso-level,
+/// found with surface nets.
+///
+/// Each cell of the grid that the surface passes through gets a single
+/// vertex, at the average of the points where the surface crosses the
+/// edges of the cell. Every edge of the grid that the surface crosses
+/// becomes a quad joining the four cells around it. Points where the field
+/// is below the iso-level are inside the surface, the quads face out.
+/// Where the surface leaves the bounds it is left open.
+#[derive(Clone)]
+pub struct SurfaceNets {
+    range: Range<usize>,
+    vertices: Vec<Vec3>,
+    faces: Vec<Quad<usize>>
+}
+
+impl SurfaceNets {
+    /// Create a new surface.
+    /// `field` is sampled at every point of the grid.
+    /// `min` and `max` are the opposite corners of the grid.
+    /// `resolution` is the number of cells along the x, y and z axes.
+    /// `iso` is the value of the field on the surface.
+    pub fn new<F>(field: F,
+                  min: Vec3,
+                  max: Vec3,
+                  resolution: (usize, usize, usize),
+                  iso: f32) -> SurfaceNets
+        where F: Fn(f32, f32, f32) -> f32 {
+
+        let grid = Grid::new(&field, min, max, resolution, iso);
+        let (vertices, faces) = grid.contour(|cell| {
+            let points = grid.crossings(cell);
+            let sum = points.iter().fold((0., 0., 0.), |sum, &p| add(sum, p));
+            scale(sum, 1. / points.len() as f32)
+        });
+
+        SurfaceNets {
+            range: 0..faces.len(),
+            vertices: vertices,
+            faces: faces
+        }
+    }
+}
+
+impl Iterator for SurfaceNets {
+    type Item = Quad<Vec3>;
+
+    fn next(&mut self) -> Option<Quad<Vec3>> {
+        self.range.next().map(|idx| {
+            self.faces[idx].map_vertex(|i| self.vertices[i])
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for SurfaceNets {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for SurfaceNets {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        self.faces[idx]
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+}
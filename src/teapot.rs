@@ -0,0 +1,165 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{MapVertex, Quad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use bezier_patch::BezierPatch;
+use math::Vec3;
+
+/// the control points of the teapot, only the part of the teapot with
+/// y <= 0 is here, the rest is made by mirroring the patches
+const CONTROL_POINTS: [(f32, f32, f32); 127] = [
+    (0.2, 0., 2.7), (0.2, -0.112, 2.7), (0.112, -0.2, 2.7), (0., -0.2, 2.7),
+    (1.3375, 0., 2.53125), (1.3375, -0.749, 2.53125), (0.749, -1.3375, 2.53125), (0., -1.3375, 2.53125),
+    (1.4375, 0., 2.53125), (1.4375, -0.805, 2.53125), (0.805, -1.4375, 2.53125), (0., -1.4375, 2.53125),
+    (1.5, 0., 2.4), (1.5, -0.84, 2.4), (0.84, -1.5, 2.4), (0., -1.5, 2.4),
+    (1.75, 0., 1.875), (1.75, -0.98, 1.875), (0.98, -1.75, 1.875), (0., -1.75, 1.875),
+    (2., 0., 1.35), (2., -1.12, 1.35), (1.12, -2., 1.35), (0., -2., 1.35),
+    (2., 0., 0.9), (2., -1.12, 0.9), (1.12, -2., 0.9), (0., -2., 0.9),
+    (-2., 0., 0.9), (2., 0., 0.45), (2., -1.12, 0.45), (1.12, -2., 0.45),
+    (0., -2., 0.45), (1.5, 0., 0.225), (1.5, -0.84, 0.225), (0.84, -1.5, 0.225),
+    (0., -1.5, 0.225), (1.5, 0., 0.15), (1.5, -0.84, 0.15), (0.84, -1.5, 0.15),
+    (0., -1.5, 0.15), (-1.6, 0., 2.025), (-1.6, -0.3, 2.025), (-1.5, -0.3, 2.25),
+    (-1.5, 0., 2.25), (-2.3, 0., 2.025), (-2.3, -0.3, 2.025), (-2.5, -0.3, 2.25),
+    (-2.5, 0., 2.25), (-2.7, 0., 2.025), (-2.7, -0.3, 2.025), (-3., -0.3, 2.25),
+    (-3., 0., 2.25), (-2.7, 0., 1.8), (-2.7, -0.3, 1.8), (-3., -0.3, 1.8),
+    (-3., 0., 1.8), (-2.7, 0., 1.575), (-2.7, -0.3, 1.575), (-3., -0.3, 1.35),
+    (-3., 0., 1.35), (-2.5, 0., 1.125), (-2.5, -0.3, 1.125), (-2.65, -0.3, 0.9375),
+    (-2.65, 0., 0.9375), (-2., -0.3, 0.9), (-1.9, -0.3, 0.6), (-1.9, 0., 0.6),
+    (1.7, 0., 1.425), (1.7, -0.66, 1.425), (1.7, -0.66, 0.6), (1.7, 0., 0.6),
+    (2.6, 0., 1.425), (2.6, -0.66, 1.425), (3.1, -0.66, 0.825), (3.1, 0., 0.825),
+    (2.3, 0., 2.1), (2.3, -0.25, 2.1), (2.4, -0.25, 2.025), (2.4, 0., 2.025),
+    (2.7, 0., 2.4), (2.7, -0.25, 2.4), (3.3, -0.25, 2.4), (3.3, 0., 2.4),
+    (2.8, 0., 2.475), (2.8, -0.25, 2.475), (3.525, -0.25, 2.49375), (3.525, 0., 2.49375),
+    (2.9, 0., 2.475), (2.9, -0.15, 2.475), (3.45, -0.15, 2.5125), (3.45, 0., 2.5125),
+    (2.8, 0., 2.4), (2.8, -0.15, 2.4), (3.2, -0.15, 2.4), (3.2, 0., 2.4),
+    (0., 0., 3.15), (0.8, 0., 3.15), (0.8, -0.45, 3.15), (0.45, -0.8, 3.15),
+    (0., -0.8, 3.15), (0., 0., 2.85), (1.4, 0., 2.4), (1.4, -0.784, 2.4),
+    (0.784, -1.4, 2.4), (0., -1.4, 2.4), (0.4, 0., 2.55), (0.4, -0.224, 2.55),
+    (0.224, -0.4, 2.55), (0., -0.4, 2.55), (1.3, 0., 2.55), (1.3, -0.728, 2.55),
+    (0.728, -1.3, 2.55), (0., -1.3, 2.55), (1.3, 0., 2.4), (1.3, -0.728, 2.4),
+    (0.728, -1.3, 2.4), (0., -1.3, 2.4), (0., 0., 0.), (1.425, -0.798, 0.),
+    (1.5, 0., 0.075), (1.425, 0., 0.), (0.798, -1.425, 0.), (0., -1.5, 0.075),
+    (0., -1.425, 0.), (1.5, -0.84, 0.075), (0.84, -1.5, 0.075)
+];
+
+/// the patches of the teapot, the first six are mirrored into all four
+/// quadrants, the handle and the spout are only mirrored across y = 0
+const PATCHES: [[usize; 16]; 10] = [
+    // rim
+    [102, 103, 104, 105,   4,   5,   6,   7,   8,   9,  10,  11,  12,  13,  14,  15],
+    // body
+    [ 12,  13,  14,  15,  16,  17,  18,  19,  20,  21,  22,  23,  24,  25,  26,  27],
+    [ 24,  25,  26,  27,  29,  30,  31,  32,  33,  34,  35,  36,  37,  38,  39,  40],
+    // lid
+    [ 96,  96,  96,  96,  97,  98,  99, 100, 101, 101, 101, 101,   0,   1,   2,   3],
+    [  0,   1,   2,   3, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117],
+    // bottom
+    [118, 118, 118, 118, 124, 122, 119, 121, 123, 126, 125, 120,  40,  39,  38,  37],
+    // handle
+    [ 41,  42,  43,  44,  45,  46,  47,  48,  49,  50,  51,  52,  53,  54,  55,  56],
+    [ 53,  54,  55,  56,  57,  58,  59,  60,  61,  62,  63,  64,  28,  65,  66,  67],
+    // spout
+    [ 68,  69,  70,  71,  72,  73,  74,  75,  76,  77,  78,  79,  80,  81,  82,  83],
+    [ 80,  81,  82,  83,  84,  85,  86,  87,  88,  89,  90,  91,  92,  93,  94,  95]
+];
+
+/// build the 32 patches of the teapot, flipping the order of the control
+/// points along u whenever a patch is mirrored an odd number of times so
+/// that it stays wound the same way
+fn patches() -> Vec<[Vec3; 16]> {
+    let mut patches = Vec::with_capacity(32);
+    for (i, patch) in PATCHES.iter().enumerate() {
+        let mirror = |sx: f32, sy: f32| {
+            let mut out = [(0., 0., 0.); 16];
+            for j in 0..4 {
+                for k in 0..4 {
+                    let k_in = if sx * sy < 0. { 3 - k } else { k };
+                    let (x, y, z) = CONTROL_POINTS[patch[4 * j + k_in]];
+                    out[4 * j + k] = (x * sx, y * sy, z);
+                }
+            }
+            out
+        };
+
+        patches.push(mirror(1., 1.));
+        patches.push(mirror(1., -1.));
+        if i < 6 {
+            patches.push(mirror(-1., 1.));
+            patches.push(mirror(-1., -1.));
+        }
+    }
+    patches
+}
+
+/// Represents the Utah teapot made of the classic 32 bicubic Bezier
+/// patches, including the bottom. The teapot stands on the z = 0 plane
+/// with its lid at z = 3.15, and the spout points along the x axis.
+///
+/// The teapot is not closed, there is a gap between the lid and the rim,
+/// and the handle and the spout pass through the body.
+#[derive(Clone)]
+pub struct Teapot {
+    range: Range<usize>,
+    patches: BezierPatch
+}
+
+impl Teapot {
+    /// Create a new teapot.
+    /// `n` is the number of quads along each side of every patch.
+    pub fn new(n: usize) -> Teapot {
+        let patches = BezierPatch::new(patches(), n, n);
+        Teapot {
+            range: 0..patches.indexed_polygon_count(),
+            patches: patches
+        }
+    }
+
+    /// Find the normal of the shared vertex `idx`, see
+    /// `BezierPatch::shared_normal`.
+    pub fn shared_normal(&self, idx: usize) -> Vec3 {
+        self.patches.shared_normal(idx)
+    }
+}
+
+impl Iterator for Teapot {
+    type Item = Quad<Vec3>;
+
+    fn next(&mut self) -> Option<Quad<Vec3>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for Teapot {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        self.patches.shared_vertex(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.patches.shared_vertex_count()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for Teapot {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        self.patches.indexed_polygon(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.patches.indexed_polygon_count()
+    }
+}
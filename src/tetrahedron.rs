@@ -0,0 +1,78 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::num::Float;
+use super::{MapVertex, Triangle};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+const VERTICES: [(f32, f32, f32); 4] = [
+    ( 1.,  1.,  1.), ( 1., -1., -1.), (-1.,  1., -1.), (-1., -1.,  1.)
+];
+
+const FACES: [[usize; 3]; 4] = [
+    [2, 1, 3], [3, 0, 2], [1, 0, 3], [2, 0, 1]
+];
+
+/// A regular tetrahedron, centered at (0, 0, 0) with each vertex 1 away from the origin
+#[derive(Clone)]
+pub struct Tetrahedron {
+    range: Range<usize>
+}
+
+impl Tetrahedron {
+    /// create a new tetrahedron generator
+    pub fn new() -> Tetrahedron {
+        Tetrahedron { range: 0..4 }
+    }
+
+    fn vert(&self, idx: usize) -> (f32, f32, f32) {
+        let (x, y, z) = VERTICES[idx];
+        let s = 1. / 3f32.sqrt();
+        (x * s, y * s, z * s)
+    }
+
+    fn face_indexed(&self, idx: usize) -> Triangle<usize> {
+        let f = FACES[idx];
+        Triangle::new(f[0], f[1], f[2])
+    }
+
+    fn face(&self, idx: usize) -> Triangle<(f32, f32, f32)> {
+        self.face_indexed(idx).map_vertex(|i| self.vert(i))
+    }
+}
+
+impl Iterator for Tetrahedron {
+    type Item = Triangle<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Triangle<(f32, f32, f32)>> {
+        self.range.next().map(|idx| self.face(idx))
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Tetrahedron {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        self.vert(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize { 4 }
+}
+
+impl IndexedPolygon<Triangle<usize>> for Tetrahedron {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        self.face_indexed(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize { 4 }
+}
@@ -0,0 +1,73 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use super::MapVertex;
+use super::generators::{SharedVertex, IndexedPolygon};
+use indexed::Indexed;
+
+/// A vertex with a position, a normal and a texture coordinate
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub struct TexturedVertex {
+    /// the position of the vertex
+    pub pos: (f32, f32, f32),
+    /// the normal of the vertex, with a length of 1
+    pub normal: (f32, f32, f32),
+    /// the texture coordinate of the vertex, (0, 0) is the bottom left
+    /// of the texture and (1, 1) is the top right
+    pub uv: (f32, f32)
+}
+
+/// build a `Textured` from vertices that already have their texture coordinates
+pub fn from_parts<P>(vertices: Vec<TexturedVertex>, polygons: Vec<P>) -> Textured<P> {
+    Textured { indexed: Indexed::new(vertices, polygons) }
+}
+
+/// A generator with texture coordinates, made by the `textured` method of
+/// `Cube`, `Plane` and `SphereUV`.
+///
+/// A vertex that needs more than one texture coordinate, like the corner of
+/// a cube or the seam of a sphere, is split. So the shared vertices and the
+/// indices are not the same as the ones of the generator it was made from.
+#[derive(Clone)]
+pub struct Textured<P> {
+    indexed: Indexed<TexturedVertex, P>
+}
+
+impl<P> Iterator for Textured<P> where P: Clone + MapVertex<usize, TexturedVertex> {
+    type Item = <P as MapVertex<usize, TexturedVertex>>::Output;
+
+    fn next(&mut self) -> Option<<P as MapVertex<usize, TexturedVertex>>::Output> {
+        self.indexed.next()
+    }
+}
+
+impl<P> SharedVertex<TexturedVertex> for Textured<P> {
+    fn shared_vertex(&self, idx: usize) -> TexturedVertex {
+        self.indexed.shared_vertex(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.indexed.shared_vertex_count()
+    }
+}
+
+impl<P: Clone> IndexedPolygon<P> for Textured<P> {
+    fn indexed_polygon(&self, idx: usize) -> P {
+        self.indexed.indexed_polygon(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.indexed.indexed_polygon_count()
+    }
+}
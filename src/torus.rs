@@ -0,0 +1,116 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::Quad;
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a torus centered at (0, 0, 0) lying in the xy plane,
+/// the z axis passes through the hole of the torus.
+#[derive(Clone, Copy)]
+pub struct Torus {
+    radius: f32,
+    tubular_radius: f32,
+    radial_segments: usize,
+    tubular_segments: usize,
+    u: usize,
+    v: usize
+}
+
+impl Torus {
+    /// Create a new torus.
+    /// `radius` is the distance from the center of the torus to the center of the tube.
+    /// `tubular_radius` is the radius of the tube.
+    /// `radial_segments` is the number of segments around the z axis.
+    /// `tubular_segments` is the number of segments around the tube.
+    pub fn new(radius: f32,
+               tubular_radius: f32,
+               radial_segments: usize,
+               tubular_segments: usize) -> Torus {
+        assert!(radial_segments > 2 && tubular_segments > 2);
+        Torus {
+            radius: radius,
+            tubular_radius: tubular_radius,
+            radial_segments: radial_segments,
+            tubular_segments: tubular_segments,
+            u: 0,
+            v: 0
+        }
+    }
+
+    fn vert(&self, u: usize, v: usize) -> (f32, f32, f32) {
+        let u = (u as f32 / self.radial_segments as f32) * PI_2;
+        let v = (v as f32 / self.tubular_segments as f32) * PI_2;
+        let r = self.radius + self.tubular_radius * v.cos();
+
+        (r * u.cos(),
+         r * u.sin(),
+         self.tubular_radius * v.sin())
+    }
+
+    fn index(&self, u: usize, v: usize) -> usize {
+        (v % self.tubular_segments) * self.radial_segments + (u % self.radial_segments)
+    }
+}
+
+impl Iterator for Torus {
+    type Item = Quad<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Quad<(f32, f32, f32)>> {
+        if self.u == self.radial_segments {
+            self.u = 0;
+            self.v += 1;
+        }
+        if self.v == self.tubular_segments {
+            return None;
+        }
+
+        let x = self.vert(self.u,   self.v);
+        let y = self.vert(self.u+1, self.v);
+        let z = self.vert(self.u+1, self.v+1);
+        let w = self.vert(self.u,   self.v+1);
+        self.u += 1;
+
+        Some(Quad::new(x, y, z, w))
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for Torus {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        let u = idx % self.radial_segments;
+        let v = idx / self.radial_segments;
+        self.vert(u, v)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.radial_segments * self.tubular_segments
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for Torus {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let u = idx % self.radial_segments;
+        let v = idx / self.radial_segments;
+
+        Quad::new(self.index(u,   v),
+                  self.index(u+1, v),
+                  self.index(u+1, v+1),
+                  self.index(u,   v+1))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.radial_segments * self.tubular_segments
+    }
+}
@@ -0,0 +1,102 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{Quad, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+use tube::Tube;
+
+/// Represents a (p, q) torus knot centered at (0, 0, 0), the knot winds
+/// around the z axis. The knot is a closed `Tube`, so it wraps
+/// around in both directions.
+#[derive(Clone)]
+pub struct TorusKnot {
+    range: Range<usize>,
+    tube: Tube,
+    tubular_segments: usize,
+    radial_segments: usize
+}
+
+impl TorusKnot {
+    /// Create a new torus knot, with a radius of 1.
+    /// `p` is the number of times the knot winds around the z axis.
+    /// `q` is the number of times the knot winds through the hole of the torus.
+    /// `tube_radius` is the radius of the tube.
+    /// `tubular_segments` is the number of segments along the knot.
+    /// `radial_segments` is the number of segments around the tube.
+    pub fn new(p: usize,
+               q: usize,
+               tube_radius: f32,
+               tubular_segments: usize,
+               radial_segments: usize) -> TorusKnot {
+        assert!(p > 0 && q > 0);
+        let (p, q) = (p as f32, q as f32);
+        let knot = move |t: f32| {
+            let u = t * p * PI_2;
+            let v = u * q / p;
+            let r = 0.5 * (2. + v.cos());
+            (r * u.cos(), r * u.sin(), 0.5 * v.sin())
+        };
+
+        TorusKnot {
+            range: 0..tubular_segments * radial_segments,
+            tube: Tube::curve(knot, tubular_segments, true, tube_radius, radial_segments),
+            tubular_segments: tubular_segments,
+            radial_segments: radial_segments
+        }
+    }
+
+    /// the index of point `j` of the cross section at point `i` of the
+    /// knot, laid out in rings like the shared vertices of `Tube`
+    fn index(&self, i: usize, j: usize) -> usize {
+        (i % self.tubular_segments) * self.radial_segments + (j % self.radial_segments)
+    }
+}
+
+impl Iterator for TorusKnot {
+    type Item = Quad<(f32, f32, f32)>;
+
+    fn next(&mut self) -> Option<Quad<(f32, f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32, f32)> for TorusKnot {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32, f32) {
+        self.tube.shared_vertex(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.tube.shared_vertex_count()
+    }
+}
+
+impl IndexedPolygon<Quad<usize>> for TorusKnot {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        let i = idx / self.radial_segments;
+        let j = idx % self.radial_segments;
+        Quad::new(self.index(i,   j),
+                  self.index(i,   j+1),
+                  self.index(i+1, j+1),
+                  self.index(i+1, j))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.tubular_segments * self.radial_segments
+    }
+}
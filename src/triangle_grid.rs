@@ -0,0 +1,104 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::num::Float;
+use super::{MapVertex, Triangle};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// Represents a 2D grid of equilateral triangles with an edge length of 1,
+/// starting at (0, 0). Every other row of vertices is shifted by half
+/// of an edge along the x axis.
+#[derive(Clone)]
+pub struct TriangleGrid {
+    range: Range<usize>,
+    width: usize,
+    height: usize
+}
+
+impl TriangleGrid {
+    /// Create a new triangle grid.
+    /// `x` is the number of pairs of triangles in each row
+    /// `y` is the number of rows
+    pub fn new(x: usize, y: usize) -> TriangleGrid {
+        assert!(x > 0 && y > 0);
+        TriangleGrid {
+            range: 0..2 * x * y,
+            width: x,
+            height: y
+        }
+    }
+
+    /// Find the grid coordinates of polygon `idx`, this is the column of the
+    /// triangle in its row followed by the row. Triangles in even columns of
+    /// even rows, and odd columns of odd rows, point up.
+    pub fn grid_coordinates(&self, idx: usize) -> (usize, usize) {
+        (idx % (2 * self.width), idx / (2 * self.width))
+    }
+
+    fn vert(&self, x: usize, y: usize) -> (f32, f32) {
+        let shift = if y % 2 == 1 { 0.5 } else { 0. };
+        (x as f32 + shift, y as f32 * 3f32.sqrt() * 0.5)
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * (self.width + 1) + x
+    }
+}
+
+impl Iterator for TriangleGrid {
+    type Item = Triangle<(f32, f32)>;
+
+    fn next(&mut self) -> Option<Triangle<(f32, f32)>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<(f32, f32)> for TriangleGrid {
+    fn shared_vertex(&self, idx: usize) -> (f32, f32) {
+        self.vert(idx % (self.width + 1), idx / (self.width + 1))
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        (self.width + 1) * (self.height + 1)
+    }
+}
+
+impl IndexedPolygon<Triangle<usize>> for TriangleGrid {
+    fn indexed_polygon(&self, idx: usize) -> Triangle<usize> {
+        let (column, y) = self.grid_coordinates(idx);
+        let x = column / 2;
+
+        match (y % 2 == 0, column % 2 == 0) {
+            (true, true) => Triangle::new(self.index(x,   y),
+                                          self.index(x+1, y),
+                                          self.index(x,   y+1)),
+            (true, false) => Triangle::new(self.index(x,   y+1),
+                                           self.index(x+1, y),
+                                           self.index(x+1, y+1)),
+            (false, true) => Triangle::new(self.index(x,   y),
+                                           self.index(x+1, y+1),
+                                           self.index(x,   y+1)),
+            (false, false) => Triangle::new(self.index(x,   y),
+                                            self.index(x+1, y),
+                                            self.index(x+1, y+1))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        2 * self.width * self.height
+    }
+}
@@ -18,6 +18,8 @@ use Polygon::{ PolyQuad, PolyTri };
 use {
     Quad,
     Triangle,
+    Pentagon,
+    Hexagon,
     Polygon,
 };
 
@@ -49,6 +51,29 @@ impl<T: Clone> EmitTriangles for Triangle<T> {
     }
 }
 
+impl<T: Clone> EmitTriangles for Pentagon<T> {
+    type Vertex = T;
+
+    fn emit_triangles<F>(&self, mut emit: F) where F: FnMut(Triangle<T>) {
+        let &Pentagon{ref x, ref y, ref z, ref w, ref v} = self;
+        emit(Triangle::new(x.clone(), y.clone(), z.clone()));
+        emit(Triangle::new(x.clone(), z.clone(), w.clone()));
+        emit(Triangle::new(x.clone(), w.clone(), v.clone()));
+    }
+}
+
+impl<T: Clone> EmitTriangles for Hexagon<T> {
+    type Vertex = T;
+
+    fn emit_triangles<F>(&self, mut emit: F) where F: FnMut(Triangle<T>) {
+        let &Hexagon{ref x, ref y, ref z, ref w, ref v, ref u} = self;
+        emit(Triangle::new(x.clone(), y.clone(), z.clone()));
+        emit(Triangle::new(x.clone(), z.clone(), w.clone()));
+        emit(Triangle::new(x.clone(), w.clone(), v.clone()));
+        emit(Triangle::new(x.clone(), v.clone(), u.clone()));
+    }
+}
+
 impl<T: Clone> EmitTriangles for Polygon<T> {
     type Vertex = T;
 
@@ -106,3 +131,82 @@ impl<V, U: EmitTriangles<Vertex=V>, SRC: Iterator<Item=U>> Iterator for Triangul
         }
     }
 }
+
+fn cross(o: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// find the signed area of a simple polygon, this is positive if
+/// the polygon is wound counter-clockwise
+pub fn signed_area(points: &[(f32, f32)]) -> f32 {
+    let mut area = 0.;
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        area += a.0 * b.1 - b.0 * a.1;
+    }
+    area * 0.5
+}
+
+/// triangulate a simple polygon, which may be concave, by ear clipping.
+/// The triangles index into `points` and are always wound counter-clockwise.
+pub fn triangulate_polygon(points: &[(f32, f32)]) -> Vec<Triangle<usize>> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < 0. {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::new();
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let mut ear = None;
+
+        for i in 0..n {
+            let (a, b, c) = (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            if cross(pa, pb, pc) <= 0. {
+                continue;
+            }
+
+            let inside = remaining.iter().any(|&j| {
+                let p = points[j];
+                j != a && j != b && j != c &&
+                cross(pa, pb, p) >= 0. &&
+                cross(pb, pc, p) >= 0. &&
+                cross(pc, pa, p) >= 0.
+            });
+            if !inside {
+                ear = Some(i);
+                break;
+            }
+        }
+
+        // a degenerate polygon may not have any ears left,
+        // clip a vertex anyway so that we always make progress
+        let i = ear.unwrap_or(0);
+        triangles.push(Triangle::new(remaining[(i + n - 1) % n],
+                                     remaining[i],
+                                     remaining[(i + 1) % n]));
+        remaining.remove(i);
+    }
+
+    if remaining.len() == 3 {
+        triangles.push(Triangle::new(remaining[0], remaining[1], remaining[2]));
+    }
+    triangles
+}
+
+#[test]
+fn test_triangulate_polygon() {
+    // an L shape, wound clockwise
+    let points = [(0., 0.), (0., 2.), (1., 2.), (1., 1.), (2., 1.), (2., 0.)];
+    let triangles = triangulate_polygon(&points);
+    assert_eq!(triangles.len(), 4);
+
+    let mut area = 0.;
+    for t in triangles.iter() {
+        let a = cross(points[t.x], points[t.y], points[t.z]);
+        assert!(a > 0.);
+        area += a * 0.5;
+    }
+    assert_eq!(area, 3.);
+}
@@ -0,0 +1,279 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use std::f32::consts::PI_2;
+use std::num::Float;
+use super::{Quad, Triangle, Polygon, MapVertex};
+use super::Polygon::{PolyTri, PolyQuad};
+use super::generators::{SharedVertex, IndexedPolygon};
+use math::{Vec3, add, sub, scale, dot, cross, normalize};
+
+/// Represents a tube built by sweeping a 2D cross section along a 3D path.
+///
+/// The cross section is oriented using rotation minimizing frames
+/// (parallel transport), so the tube does not twist around the path.
+///
+/// The sides of the tube are always quads, but the caps are fans of
+/// triangles. Since the caps are turned on and off at runtime the tube
+/// yields a `Polygon`, without caps every polygon is a `PolyQuad`.
+#[derive(Clone)]
+pub struct Tube {
+    range: Range<usize>,
+    path: Vec<Vec3>,
+    radii: Vec<f32>,
+    section: Vec<(f32, f32)>,
+    closed: bool,
+    caps: bool,
+    normals: Vec<Vec3>,
+    binormals: Vec<Vec3>
+}
+
+impl Tube {
+    /// Create a new open tube with a circular cross section.
+    /// `path` is the list of points the tube follows, two points in a row
+    /// can not be the same.
+    /// `radius` is the radius of the tube.
+    /// `u` is the number of points around the tube.
+    pub fn new(path: Vec<Vec3>, radius: f32, u: usize) -> Tube {
+        assert!(path.len() > 1 && u > 2);
+        for i in 1..path.len() {
+            if path[i - 1] == path[i] {
+                panic!("{} point of the path is the same as the one before it", i)
+            }
+        }
+        let section = (0..u).map(|i| {
+            let a = (i as f32 / u as f32) * PI_2;
+            (a.cos(), a.sin())
+        }).collect();
+
+        let mut tube = Tube {
+            range: 0..0,
+            radii: path.iter().map(|_| radius).collect(),
+            path: path,
+            section: section,
+            closed: false,
+            caps: false,
+            normals: Vec::new(),
+            binormals: Vec::new()
+        };
+        tube.build();
+        tube
+    }
+
+    /// Create a new tube with a circular cross section that follows the curve `f`.
+    /// `f` maps a parameter from 0 to 1 to a point on the curve.
+    /// `samples` is the number of points taken along the curve.
+    /// `closed` joins the end of the curve back to its start, the point at 1 is
+    /// then not sampled as it is expected to be the same as the point at 0.
+    /// `radius` is the radius of the tube.
+    /// `u` is the number of points around the tube.
+    pub fn curve<F>(f: F, samples: usize, closed: bool, radius: f32, u: usize) -> Tube
+        where F: Fn(f32) -> Vec3 {
+
+        assert!(samples > 1);
+        let steps = if closed { samples } else { samples - 1 };
+        let path = (0..samples).map(|i| f(i as f32 / steps as f32)).collect();
+        Tube::new(path, radius, u).closed(closed)
+    }
+
+    /// Set the radius of the tube at each point of the path, this
+    /// can be used to taper the tube.
+    pub fn radii(mut self, radii: Vec<f32>) -> Tube {
+        assert!(radii.len() == self.path.len());
+        self.radii = radii;
+        self
+    }
+
+    /// Replace the circular cross section with `section`, a closed
+    /// polygon that is wound counter-clockwise and is scaled by the radius.
+    pub fn section(mut self, section: Vec<(f32, f32)>) -> Tube {
+        assert!(section.len() > 2);
+        self.section = section;
+        self.build();
+        self
+    }
+
+    /// Join the end of the path back to its start, this makes the
+    /// tube a closed loop. A closed tube can not have caps, and the end
+    /// of the path can not be the same as its start.
+    pub fn closed(mut self, closed: bool) -> Tube {
+        assert!(!(closed && self.caps));
+        assert!(!(closed && self.path[0] == self.path[self.path.len() - 1]));
+        self.closed = closed;
+        self.build();
+        self
+    }
+
+    /// Turn the caps at the start and end of the path on or off. Each
+    /// cap is a fan of triangles around the point on the path.
+    pub fn caps(mut self, caps: bool) -> Tube {
+        assert!(!(caps && self.closed));
+        self.caps = caps;
+        self.build();
+        self
+    }
+
+    fn tangent(&self, i: usize) -> Vec3 {
+        let n = self.path.len();
+        let (a, b) = if self.closed {
+            (self.path[(i + n - 1) % n], self.path[(i + 1) % n])
+        } else if i == 0 {
+            (self.path[0], self.path[1])
+        } else if i == n - 1 {
+            (self.path[n - 2], self.path[n - 1])
+        } else {
+            (self.path[i - 1], self.path[i + 1])
+        };
+
+        // the path can turn back on itself, then the points on either
+        // side are the same and the next segment is used instead
+        if a == b {
+            normalize(sub(self.path[(i + 1) % n], self.path[i]))
+        } else {
+            normalize(sub(b, a))
+        }
+    }
+
+    /// calculate the rotation minimizing frames using the double
+    /// reflection method, from Wang et al. "Computation of Rotation
+    /// Minimizing Frames"
+    fn build(&mut self) {
+        let n = self.path.len();
+        let tangents: Vec<Vec3> = (0..n).map(|i| self.tangent(i)).collect();
+
+        // start with any normal that is perpendicular to the first tangent
+        let t = tangents[0];
+        let axis = if t.0.abs() <= t.1.abs() && t.0.abs() <= t.2.abs() {
+            (1., 0., 0.)
+        } else if t.1.abs() <= t.2.abs() {
+            (0., 1., 0.)
+        } else {
+            (0., 0., 1.)
+        };
+        let mut normal = normalize(sub(axis, scale(t, dot(t, axis))));
+
+        let steps = if self.closed { n } else { n - 1 };
+        let mut normals = vec![normal];
+        for i in 0..steps {
+            let (t0, t1) = (tangents[i], tangents[(i + 1) % n]);
+            let v1 = sub(self.path[(i + 1) % n], self.path[i]);
+            let c1 = dot(v1, v1);
+            let (r, t) = if c1 > 0. {
+                (sub(normal, scale(v1, 2. / c1 * dot(v1, normal))),
+                 sub(t0, scale(v1, 2. / c1 * dot(v1, t0))))
+            } else {
+                (normal, t0)
+            };
+
+            let v2 = sub(t1, t);
+            let c2 = dot(v2, v2);
+            normal = if c2 > 0. {
+                sub(r, scale(v2, 2. / c2 * dot(v2, r)))
+            } else {
+                r
+            };
+            normals.push(normal);
+        }
+
+        // a closed loop will not generally meet up with the starting frame,
+        // spread the difference evenly along the path
+        if self.closed {
+            let end = normals.pop().unwrap();
+            let angle = dot(cross(end, normals[0]), tangents[0]).atan2(dot(end, normals[0]));
+            for i in 0..n {
+                let a = angle * i as f32 / n as f32;
+                let b = cross(tangents[i], normals[i]);
+                normals[i] = add(scale(normals[i], a.cos()), scale(b, a.sin()));
+            }
+        }
+
+        self.binormals = (0..n).map(|i| cross(tangents[i], normals[i])).collect();
+        self.normals = normals;
+        self.range = 0..self.indexed_polygon_count();
+    }
+
+    fn ring_count(&self) -> usize {
+        self.path.len() * self.section.len()
+    }
+
+    fn bands(&self) -> usize {
+        if self.closed { self.path.len() } else { self.path.len() - 1 }
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        (i % self.path.len()) * self.section.len() + (j % self.section.len())
+    }
+}
+
+impl Iterator for Tube {
+    type Item = Polygon<Vec3>;
+
+    fn next(&mut self) -> Option<Polygon<Vec3>> {
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx).map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl SharedVertex<Vec3> for Tube {
+    fn shared_vertex(&self, idx: usize) -> Vec3 {
+        if idx < self.ring_count() {
+            let i = idx / self.section.len();
+            let (x, y) = self.section[idx % self.section.len()];
+            let offset = add(scale(self.normals[i], x), scale(self.binormals[i], y));
+            add(self.path[i], scale(offset, self.radii[i]))
+        } else if idx == self.ring_count() {
+            self.path[0]
+        } else {
+            self.path[self.path.len() - 1]
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.ring_count() + if self.caps { 2 } else { 0 }
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Tube {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let m = self.section.len();
+        let caps = if self.caps { m } else { 0 };
+
+        if idx < caps {
+            PolyTri(Triangle::new(self.ring_count(),
+                                  self.index(0, idx+1),
+                                  self.index(0, idx)))
+        } else if idx < caps + self.bands() * m {
+            let idx = idx - caps;
+            let i = idx / m;
+            let j = idx % m;
+            PolyQuad(Quad::new(self.index(i,   j),
+                               self.index(i,   j+1),
+                               self.index(i+1, j+1),
+                               self.index(i+1, j)))
+        } else {
+            let j = idx - caps - self.bands() * m;
+            let i = self.path.len() - 1;
+            PolyTri(Triangle::new(self.ring_count() + 1,
+                                  self.index(i, j),
+                                  self.index(i, j+1)))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        let caps = if self.caps { 2 * self.section.len() } else { 0 };
+        self.bands() * self.section.len() + caps
+    }
+}
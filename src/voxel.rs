@@ -0,0 +1,218 @@
+//   Copyright Colin Sherratt 2014
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+use std::ops::Range;
+use super::{Quad, MapVertex};
+use super::generators::{SharedVertex, IndexedPolygon};
+
+/// A vertex of a face of a block of `Voxels`
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub struct VoxelVertex<T> {
+    /// the position of the vertex, each block is 1 unit across
+    pub position: (f32, f32, f32),
+    /// the direction that the face points in, along one of the axes
+    pub normal: (f32, f32, f32),
+    /// the block that the face belongs to
+    pub material: T
+}
+
+/// Represents the visible faces of a volume of blocks.
+///
+/// The volume is laid out like a `[x][y][z]` array, block (x, y, z) is
+/// `blocks[(x * size.1 + y) * size.2 + z]` and covers the unit cube from
+/// (x, y, z) to (x + 1, y + 1, z + 1). Transparent blocks have no faces, a
+/// face of any other block is visible when the block next to it is
+/// transparent or outside of the volume.
+///
+/// The faces are grouped by the direction they point in, in the order -x,
+/// +x, -y, +y, -z, +z, and are wound counter-clockwise when viewed from
+/// outside. Each face has its own four vertices.
+#[derive(Clone)]
+pub struct Voxels<T> {
+    range: Range<usize>,
+    vertices: Vec<VoxelVertex<T>>,
+    faces: Vec<Quad<usize>>
+}
+
+impl<T: Copy + PartialEq> Voxels<T> {
+    /// Create a quad for each visible face of every block.
+    /// `size` is the number of blocks along the x, y and z axes.
+    /// `blocks` is the volume, see `Voxels`.
+    /// `transparent` tells which blocks can be seen through.
+    pub fn culled<F>(size: (usize, usize, usize), blocks: Vec<T>, transparent: F) -> Voxels<T>
+        where F: Fn(T) -> bool {
+        Voxels::build(size, blocks, transparent, false)
+    }
+
+    /// Create the visible faces of the blocks, merging neighbouring faces
+    /// of the same material that point the same way into rectangles. This
+    /// gives far fewer quads than `culled`, but the quads may meet in
+    /// T-junctions. See `Voxels::culled`.
+    pub fn greedy<F>(size: (usize, usize, usize), blocks: Vec<T>, transparent: F) -> Voxels<T>
+        where F: Fn(T) -> bool {
+        Voxels::build(size, blocks, transparent, true)
+    }
+
+    fn build<F>(size: (usize, usize, usize), blocks: Vec<T>, transparent: F, greedy: bool) -> Voxels<T>
+        where F: Fn(T) -> bool {
+
+        let size = [size.0, size.1, size.2];
+        assert_eq!(blocks.len(), size[0] * size[1] * size[2]);
+        let block = |p: [usize; 3]| blocks[(p[0] * size[1] + p[1]) * size[2] + p[2]];
+
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+
+        for face in 0..6 {
+            let (d, positive) = (face / 2, face % 2 == 1);
+            let (u, v) = ((d + 1) % 3, (d + 2) % 3);
+            let (su, sv) = (size[u], size[v]);
+
+            for layer in 0..size[d] {
+                // the material of each visible face in this layer
+                let mut mask = Vec::with_capacity(su * sv);
+                for j in 0..sv {
+                    for i in 0..su {
+                        let mut p = [0; 3];
+                        p[d] = layer;
+                        p[u] = i;
+                        p[v] = j;
+
+                        let material = block(p);
+                        let mut q = p;
+                        let neighbour = if positive && p[d] + 1 < size[d] {
+                            q[d] += 1;
+                            Some(block(q))
+                        } else if !positive && p[d] > 0 {
+                            q[d] -= 1;
+                            Some(block(q))
+                        } else {
+                            None
+                        };
+                        let covered = match neighbour {
+                            Some(neighbour) => !transparent(neighbour),
+                            None => false
+                        };
+
+                        mask.push(if transparent(material) || covered {
+                            None
+                        } else {
+                            Some(material)
+                        });
+                    }
+                }
+
+                for j in 0..sv {
+                    let mut i = 0;
+                    while i < su {
+                        let material = match mask[j * su + i] {
+                            Some(material) => material,
+                            None => {
+                                i += 1;
+                                continue;
+                            }
+                        };
+
+                        // grow the face along u, then along v while every
+                        // face in the next row matches
+                        let (mut w, mut h) = (1, 1);
+                        if greedy {
+                            while i + w < su && mask[j * su + i + w] == Some(material) {
+                                w += 1;
+                            }
+                            'rows: while j + h < sv {
+                                for k in 0..w {
+                                    if mask[(j + h) * su + i + k] != Some(material) {
+                                        break 'rows;
+                                    }
+                                }
+                                h += 1;
+                            }
+                        }
+
+                        for jj in j..j + h {
+                            for ii in i..i + w {
+                                mask[jj * su + ii] = None;
+                            }
+                        }
+
+                        let mut normal = [0.; 3];
+                        normal[d] = if positive { 1. } else { -1. };
+                        let plane = if positive { layer + 1 } else { layer };
+                        let mut corner = |a: usize, b: usize| {
+                            let mut p = [0.; 3];
+                            p[d] = plane as f32;
+                            p[u] = a as f32;
+                            p[v] = b as f32;
+                            vertices.push(VoxelVertex {
+                                position: (p[0], p[1], p[2]),
+                                normal: (normal[0], normal[1], normal[2]),
+                                material: material
+                            });
+                            vertices.len() - 1
+                        };
+
+                        let a = corner(i,     j);
+                        let b = corner(i + w, j);
+                        let c = corner(i + w, j + h);
+                        let e = corner(i,     j + h);
+                        faces.push(if positive {
+                            Quad::new(a, b, c, e)
+                        } else {
+                            Quad::new(a, e, c, b)
+                        });
+
+                        i += w;
+                    }
+                }
+            }
+        }
+
+        Voxels {
+            range: 0..faces.len(),
+            vertices: vertices,
+            faces: faces
+        }
+    }
+}
+
+impl<T: Copy> Iterator for Voxels<T> {
+    type Item = Quad<VoxelVertex<T>>;
+
+    fn next(&mut self) -> Option<Quad<VoxelVertex<T>>> {
+        self.range.next().map(|idx| {
+            self.faces[idx].map_vertex(|i| self.vertices[i])
+        })
+    }
+}
+
+impl<T: Copy> SharedVertex<VoxelVertex<T>> for Voxels<T> {
+    fn shared_vertex(&self, idx: usize) -> VoxelVertex<T> {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl<T> IndexedPolygon<Quad<usize>> for Voxels<T> {
+    fn indexed_polygon(&self, idx: usize) -> Quad<usize> {
+        self.faces[idx]
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+}
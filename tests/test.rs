@@ -12,8 +12,12 @@
 //   See the License for the specific language governing permissions and
 //   limitations under the License.
 
+#![feature(core)]
+
 extern crate genmesh;
 
+use std::num::Float;
+
 use genmesh::{
     Quad,
     EmitTriangles,
@@ -22,10 +26,23 @@ use genmesh::{
     LruIndexer,
     Indexer,
     Vertices,
-    Triangulate
+    Triangulate,
+    Polygon,
+    Pentagon,
+    Vertex,
+    MapVertex,
+    TexturedVertex
 };
 
-use genmesh::generators::{Cube, Plane};
+use genmesh::generators::{Cube, Plane, Cylinder, Torus, IcoSphere, Cone};
+use genmesh::generators::{Capsule, Tetrahedron, Octahedron, Dodecahedron, Icosahedron};
+use genmesh::generators::{Circle, Annulus, ParametricSurface, Lathe};
+use genmesh::generators::{Extrusion, Tube, Heightmap, TriangleGrid, HexGrid};
+use genmesh::generators::{RoundedBox, SphereUV};
+use genmesh::generators::{TorusKnot, Superellipsoid, Supertoroid, CubeSphere};
+use genmesh::generators::{BezierPatch, Teapot, MarchingCubes};
+use genmesh::generators::{SurfaceNets, DualContouring, Voxels, VoxelVertex, Normals};
+use genmesh::generators::{SharedVertex, IndexedPolygon};
 
 #[test]
 fn test_quad_vertex() {
@@ -187,3 +204,1118 @@ fn test_lru_indexer() {
     assert_eq!(20, vectices.len());
     assert_eq!(3*6*2, indexes.len());
 }
+
+fn assert_outward<I: Iterator<Item=Triangle<(f32, f32, f32)>>>(iter: I) {
+    for Triangle{x: a, y: b, z: c} in iter {
+        let (ux, uy, uz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+        let (vx, vy, vz) = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+        let n = (uy*vz - uz*vy, uz*vx - ux*vz, ux*vy - uy*vx);
+        let m = (a.0 + b.0 + c.0, a.1 + b.1 + c.1, a.2 + b.2 + c.2);
+        assert!(n.0*m.0 + n.1*m.1 + n.2*m.2 > 0.);
+    }
+}
+
+#[test]
+fn test_cylinder() {
+    let cylinder = Cylinder::new(8);
+    assert_eq!(cylinder.shared_vertex_count(), 18);
+    assert_eq!(cylinder.indexed_polygon_count(), 24);
+    assert_eq!(cylinder.clone().count(), 24);
+    for i in cylinder.indexed_polygon_iter().vertices() {
+        assert!(i < cylinder.shared_vertex_count());
+    }
+    assert_outward(cylinder.triangulate());
+
+    let cylinder = Cylinder::subdivide(8, 3).caps(false, true);
+    assert_eq!(cylinder.shared_vertex_count(), 33);
+    assert_eq!(cylinder.indexed_polygon_count(), 32);
+    assert_eq!(cylinder.clone().count(), 32);
+    assert_outward(cylinder.triangulate());
+}
+
+#[test]
+fn test_torus() {
+    let torus = Torus::new(1., 0.25, 8, 6);
+    assert_eq!(torus.shared_vertex_count(), 48);
+    assert_eq!(torus.indexed_polygon_count(), 48);
+    assert_eq!(torus.count(), 48);
+
+    // the seams wrap around, so no two shared vertices may be the same
+    let verts: Vec<(f32, f32, f32)> = torus.shared_vertex_iter().collect();
+    for (i, a) in verts.iter().enumerate() {
+        for b in verts[i+1..].iter() {
+            assert!(a != b);
+        }
+    }
+    for i in torus.indexed_polygon_iter().vertices() {
+        assert!(i < torus.shared_vertex_count());
+    }
+
+    // each face should point away from the center of the tube
+    for Triangle{x: a, y: b, z: c} in torus.triangulate() {
+        let (ux, uy, uz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+        let (vx, vy, vz) = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+        let n = (uy*vz - uz*vy, uz*vx - ux*vz, ux*vy - uy*vx);
+        let l = (a.0*a.0 + a.1*a.1).sqrt();
+        let m = (a.0 - a.0 / l, a.1 - a.1 / l, a.2);
+        assert!(n.0*m.0 + n.1*m.1 + n.2*m.2 > 0.);
+    }
+}
+
+#[test]
+fn test_icosphere() {
+    for n in 0..4 {
+        let sphere = IcoSphere::subdivide(n);
+        let faces = 20 * 4usize.pow(n as u32);
+        assert_eq!(sphere.shared_vertex_count(), faces / 2 + 2);
+        assert_eq!(sphere.indexed_polygon_count(), faces);
+        assert_eq!(sphere.clone().count(), faces);
+
+        for (x, y, z) in sphere.shared_vertex_iter() {
+            assert!((x*x + y*y + z*z - 1.).abs() < 1e-5);
+        }
+        for i in sphere.indexed_polygon_iter().vertices() {
+            assert!(i < sphere.shared_vertex_count());
+        }
+        assert_outward(sphere);
+    }
+}
+
+#[test]
+fn test_cone() {
+    let cone = Cone::new(8);
+    assert_eq!(cone.shared_vertex_count(), 10);
+    assert_eq!(cone.indexed_polygon_count(), 16);
+    assert_eq!(cone.clone().count(), 16);
+    for p in cone.indexed_polygon_iter() {
+        match p {
+            Polygon::PolyTri(_) => (),
+            Polygon::PolyQuad(_) => panic!("a single segment cone has no quads")
+        }
+    }
+    assert_outward(cone.triangulate());
+
+    let frustum = Cone::frustum(1., 0.5, 8, 2);
+    assert_eq!(frustum.shared_vertex_count(), 26);
+    assert_eq!(frustum.indexed_polygon_count(), 32);
+    assert_outward(frustum.triangulate());
+
+    let cone = Cone::frustum(0., 1., 6, 3).caps(true, true);
+    assert_eq!(cone.shared_vertex_count(), 20);
+    assert_eq!(cone.indexed_polygon_count(), 24);
+    let quads = cone.indexed_polygon_iter().filter(|p| match p {
+        &Polygon::PolyQuad(_) => true,
+        _ => false
+    }).count();
+    assert_eq!(quads, 12);
+    for i in cone.indexed_polygon_iter().vertices() {
+        assert!(i < cone.shared_vertex_count());
+    }
+    assert_outward(cone.triangulate());
+}
+
+#[test]
+fn test_capsule() {
+    let capsule = Capsule::new(0.5, 2., 8, 3);
+    assert_eq!(capsule.shared_vertex_count(), 50);
+    assert_eq!(capsule.indexed_polygon_count(), 56);
+    assert_eq!(capsule.count(), 56);
+
+    for (x, y, z) in capsule.shared_vertex_iter() {
+        let d = z.abs() - 1.;
+        let d = if d > 0. { d } else { 0. };
+        assert!(((x*x + y*y + d*d).sqrt() - 0.5).abs() < 1e-5);
+    }
+    for i in capsule.indexed_polygon_iter().vertices() {
+        assert!(i < capsule.shared_vertex_count());
+    }
+    assert_outward(capsule.triangulate());
+}
+
+fn assert_closed<P: EmitTriangles<Vertex=usize>, I: Iterator<Item=P>>(iter: I) {
+    // every edge of a closed mesh is used once in each direction
+    let mut edges = Vec::new();
+    for Triangle{x, y, z} in iter.triangulate() {
+        edges.push((x, y));
+        edges.push((y, z));
+        edges.push((z, x));
+    }
+    for &(a, b) in edges.iter() {
+        assert_eq!(edges.iter().filter(|&&e| e == (a, b)).count(), 1);
+        assert_eq!(edges.iter().filter(|&&e| e == (b, a)).count(), 1);
+    }
+}
+
+#[test]
+fn test_platonic_solids() {
+    assert_eq!(Tetrahedron::new().count(), 4);
+    assert_eq!(Octahedron::new().count(), 8);
+    assert_eq!(Dodecahedron::new().count(), 12);
+    assert_eq!(Icosahedron::new().count(), 20);
+
+    assert_eq!(Tetrahedron::new().shared_vertex_count(), 4);
+    assert_eq!(Octahedron::new().shared_vertex_count(), 6);
+    assert_eq!(Dodecahedron::new().shared_vertex_count(), 20);
+    assert_eq!(Icosahedron::new().shared_vertex_count(), 12);
+
+    assert_outward(Tetrahedron::new());
+    assert_outward(Octahedron::new());
+    assert_outward(Dodecahedron::new().triangulate());
+    assert_outward(Icosahedron::new());
+    assert_outward(Cube::new().triangulate());
+
+    assert_closed(Tetrahedron::new().indexed_polygon_iter());
+    assert_closed(Octahedron::new().indexed_polygon_iter());
+    assert_closed(Dodecahedron::new().indexed_polygon_iter());
+    assert_closed(Icosahedron::new().indexed_polygon_iter());
+    assert_closed(Cube::new().indexed_polygon_iter());
+
+    let mut verts: Vec<(f32, f32, f32)> = Vec::new();
+    verts.extend(Tetrahedron::new().shared_vertex_iter());
+    verts.extend(Octahedron::new().shared_vertex_iter());
+    verts.extend(Dodecahedron::new().shared_vertex_iter());
+    verts.extend(Icosahedron::new().shared_vertex_iter());
+    for (x, y, z) in verts {
+        assert!((x*x + y*y + z*z - 1.).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_pentagon() {
+    let p = Pentagon::new(0usize, 1, 2, 3, 4);
+    let mut result = Vec::new();
+    p.emit_triangles(|v| result.push(v));
+    assert_eq!(result, vec![Triangle::new(0usize, 1, 2),
+                            Triangle::new(0usize, 2, 3),
+                            Triangle::new(0usize, 3, 4)]);
+
+    let v: Vec<usize> = vec![p].into_iter().vertex(|v| v * 2).vertices().collect();
+    assert_eq!(v, vec![0, 2, 4, 6, 8]);
+}
+
+fn assert_ccw<I: Iterator<Item=Triangle<(f32, f32)>>>(iter: I) {
+    for Triangle{x: a, y: b, z: c} in iter {
+        assert!((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0) > 0.);
+    }
+}
+
+#[test]
+fn test_circle() {
+    let circle = Circle::new(8);
+    assert_eq!(circle.shared_vertex_count(), 9);
+    assert_eq!(circle.indexed_polygon_count(), 8);
+    assert_eq!(circle.shared_vertex(0), (0., 0.));
+    assert_ccw(circle.clone());
+
+    let circle = Circle::ngon(8);
+    assert_eq!(circle.shared_vertex_count(), 8);
+    assert_eq!(circle.indexed_polygon_count(), 6);
+    assert_ccw(circle.clone());
+    for i in circle.indexed_polygon_iter().vertices() {
+        assert!(i < circle.shared_vertex_count());
+    }
+}
+
+#[test]
+fn test_annulus() {
+    let annulus = Annulus::new(0.5, 1., 8, 2);
+    assert_eq!(annulus.shared_vertex_count(), 24);
+    assert_eq!(annulus.indexed_polygon_count(), 16);
+    assert_eq!(annulus.count(), 16);
+    assert_ccw(annulus.triangulate());
+
+    for (x, y) in annulus.shared_vertex_iter() {
+        let r = (x*x + y*y).sqrt();
+        assert!(r > 0.5 - 1e-5 && r < 1. + 1e-5);
+    }
+    for i in annulus.indexed_polygon_iter().vertices() {
+        assert!(i < annulus.shared_vertex_count());
+    }
+}
+
+#[test]
+fn test_cube_subdivide() {
+    let cube = Cube::subdivide(2, 2, 2);
+    assert_eq!(cube.shared_vertex_count(), 26);
+    assert_eq!(cube.indexed_polygon_count(), 24);
+    assert_eq!(cube.clone().count(), 24);
+    assert_outward(cube.clone().triangulate());
+    assert_closed(cube.indexed_polygon_iter());
+
+    let cube = Cube::subdivide(3, 2, 4);
+    assert_eq!(cube.shared_vertex_count(), 54);
+    assert_eq!(cube.indexed_polygon_count(), 52);
+    assert_outward(cube.clone().triangulate());
+    assert_closed(cube.indexed_polygon_iter());
+
+    // every vertex is on the surface and is only emitted once
+    let verts: Vec<(f32, f32, f32)> = cube.shared_vertex_iter().collect();
+    for (i, a) in verts.iter().enumerate() {
+        assert!(a.0.abs() == 1. || a.1.abs() == 1. || a.2.abs() == 1.);
+        for b in verts[i+1..].iter() {
+            assert!(a != b);
+        }
+    }
+}
+
+#[test]
+fn test_parametric_surface() {
+    let plane = ParametricSurface::new(2, 2, |u, v| (u * 2. - 1., v * 2. - 1., 0.));
+    assert_eq!(plane.shared_vertex_count(), Plane::subdivide(2, 2).shared_vertex_count());
+    assert_eq!(plane.indexed_polygon_count(), 4);
+    for (a, b) in plane.shared_vertex_iter().zip(Plane::subdivide(2, 2).shared_vertex_iter()) {
+        assert_eq!(a, (b.0, b.1, 0.));
+    }
+    for Triangle{x: a, y: b, z: c} in plane.triangulate() {
+        assert!((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0) > 0.);
+    }
+
+    fn torus(u: f32, v: f32) -> (f32, f32, f32) {
+        let tau = 2. * std::f32::consts::PI;
+        let r = 1. + 0.25 * (v * tau).cos();
+        (r * (u * tau).cos(), r * (u * tau).sin(), 0.25 * (v * tau).sin())
+    }
+    let surface = ParametricSurface::new(8, 6, torus).wrap(true, true);
+    assert_eq!(surface.shared_vertex_count(), Torus::new(1., 0.25, 8, 6).shared_vertex_count());
+    assert_eq!(surface.indexed_polygon_count(), 48);
+    for (i, j) in surface.indexed_polygon_iter().vertices()
+                         .zip(Torus::new(1., 0.25, 8, 6).indexed_polygon_iter().vertices()) {
+        assert_eq!(i, j);
+    }
+
+    let tube = ParametricSurface::new(8, 3, torus).wrap(true, false);
+    assert_eq!(tube.shared_vertex_count(), 32);
+    assert_eq!(tube.count(), 24);
+}
+
+#[test]
+fn test_lathe() {
+    // a closed cylinder
+    let profile = vec![(0., -1.), (1., -1.), (1., 1.), (0., 1.)];
+    let lathe = Lathe::new(profile.clone(), 8);
+    assert_eq!(lathe.shared_vertex_count(), 18);
+    assert_eq!(lathe.indexed_polygon_count(), 24);
+    assert_eq!(lathe.clone().count(), 24);
+    assert_outward(lathe.clone().triangulate());
+    assert_closed(lathe.indexed_polygon_iter());
+
+    let tris = lathe.indexed_polygon_iter().filter(|p| match p {
+        &Polygon::PolyTri(_) => true,
+        _ => false
+    }).count();
+    assert_eq!(tris, 16);
+
+    // half of the same cylinder needs a seam column
+    let lathe = Lathe::new(profile, 8).sweep(std::f32::consts::PI);
+    assert_eq!(lathe.shared_vertex_count(), 20);
+    assert_eq!(lathe.indexed_polygon_count(), 24);
+    for i in lathe.indexed_polygon_iter().vertices() {
+        assert!(i < lathe.shared_vertex_count());
+    }
+    assert_outward(lathe.triangulate());
+
+    // segments along the axis produce no faces
+    let lathe = Lathe::new(vec![(0., -1.), (0., 0.), (1., 0.), (0., 1.)], 6);
+    assert_eq!(lathe.shared_vertex_count(), 9);
+    assert_eq!(lathe.indexed_polygon_count(), 12);
+}
+
+fn signed_volume<I: Iterator<Item=Triangle<(f32, f32, f32)>>>(iter: I) -> f32 {
+    let mut volume = 0.;
+    for Triangle{x: a, y: b, z: c} in iter {
+        let n = (b.1*c.2 - b.2*c.1, b.2*c.0 - b.0*c.2, b.0*c.1 - b.1*c.0);
+        volume += (a.0*n.0 + a.1*n.1 + a.2*n.2) / 6.;
+    }
+    volume
+}
+
+#[test]
+fn test_extrusion() {
+    // an L shape, wound clockwise
+    let outline = vec![(0., 0.), (0., 2.), (1., 2.), (1., 1.), (2., 1.), (2., 0.)];
+    let extrusion = Extrusion::new(outline, 1.5, 2);
+    assert_eq!(extrusion.shared_vertex_count(), 30);
+    assert_eq!(extrusion.indexed_polygon_count(), 20);
+    assert_eq!(extrusion.clone().count(), 20);
+    for i in extrusion.indexed_polygon_iter().vertices() {
+        assert!(i < extrusion.shared_vertex_count());
+    }
+
+    // a consistently outward facing mesh encloses a positive volume
+    let volume = signed_volume(extrusion.triangulate());
+    assert!((volume - 4.5).abs() < 1e-5);
+}
+
+#[test]
+fn test_tube() {
+    // a straight tube with caps is a cylinder
+    let tube = Tube::new(vec![(0., 0., -1.), (0., 0., 0.), (0., 0., 1.)], 1., 8).caps(true);
+    assert_eq!(tube.shared_vertex_count(), 26);
+    assert_eq!(tube.indexed_polygon_count(), 32);
+    assert_eq!(tube.clone().count(), 32);
+    assert_outward(tube.clone().triangulate());
+    assert_closed(tube.indexed_polygon_iter());
+
+    // a tapered tube along a helix
+    let path: Vec<(f32, f32, f32)> = (0..32).map(|i| {
+        let a = i as f32 * 0.3;
+        (a.cos(), a.sin(), a * 0.2)
+    }).collect();
+    let radii = (0..32).map(|i| 0.2 - i as f32 * 0.005).collect();
+    let tube = Tube::new(path.clone(), 0.2, 6).radii(radii).caps(true);
+    assert_eq!(tube.shared_vertex_count(), 32 * 6 + 2);
+    assert_eq!(tube.indexed_polygon_count(), 31 * 6 + 12);
+    assert_closed(tube.indexed_polygon_iter());
+    assert!(signed_volume(tube.clone().triangulate()) > 0.);
+
+    // the frames should not twist, so the first point of the
+    // cross section moves smoothly from ring to ring
+    let verts: Vec<(f32, f32, f32)> = tube.shared_vertex_iter().collect();
+    for i in 0..31 {
+        let (a, b) = (verts[i * 6], verts[(i + 1) * 6]);
+        let d = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2) + (a.2 - b.2).powi(2)).sqrt();
+        assert!(d < 0.4);
+    }
+
+    // a closed loop wraps the seam
+    let path: Vec<(f32, f32, f32)> = (0..16).map(|i| {
+        let a = i as f32 / 16. * 2. * std::f32::consts::PI;
+        (a.cos(), a.sin(), (a * 2.).sin() * 0.3)
+    }).collect();
+    let tube = Tube::new(path, 0.25, 8).closed(true);
+    assert_eq!(tube.shared_vertex_count(), 128);
+    assert_eq!(tube.indexed_polygon_count(), 128);
+    assert_closed(tube.indexed_polygon_iter());
+
+    // including across the seam
+    let verts: Vec<(f32, f32, f32)> = tube.shared_vertex_iter().collect();
+    for i in 0..16 {
+        let (a, b) = (verts[i * 8], verts[((i + 1) % 16) * 8]);
+        let d = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2) + (a.2 - b.2).powi(2)).sqrt();
+        assert!(d < 0.6);
+    }
+    assert!(signed_volume(tube.triangulate()) > 0.);
+
+    // a path that turns back on itself
+    let tube = Tube::new(vec![(0., 0., 0.), (0., 0., 1.), (0., 0., 0.)], 0.5, 4);
+    for (x, y, z) in tube.shared_vertex_iter() {
+        assert!(x.is_finite() && y.is_finite() && z.is_finite());
+    }
+    for p in tube {
+        match p {
+            Polygon::PolyQuad(_) => (),
+            Polygon::PolyTri(_) => panic!("a tube without caps only has quads")
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_tube_repeated_point() {
+    Tube::new(vec![(0., 0., 0.), (0., 0., 1.), (0., 0., 1.)], 0.5, 4);
+}
+
+#[test]
+fn test_heightmap() {
+    let samples = vec![0., 1., 0.,
+                       1., 0., 1.,
+                       0., 1., 2.];
+    let map = Heightmap::new(3, 3, samples.clone()).scale(2., 0.5);
+    let plane = Plane::subdivide(2, 2);
+    assert_eq!(map.shared_vertex_count(), 9);
+    assert_eq!(map.indexed_polygon_count(), 4);
+    assert_eq!(map.clone().count(), 4);
+
+    for (i, (x, y, z)) in map.shared_vertex_iter().enumerate() {
+        let (px, py) = plane.shared_vertex(i);
+        assert_eq!((x, y, z), (px * 2., py * 2., samples[i] * 0.5));
+    }
+    for (a, b) in map.indexed_polygon_iter().zip(plane.indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+
+    // each quad is rotated so that it is split along its shorter diagonal
+    let map = map.best_diagonal(true);
+    for Quad{x, z, ..} in map.indexed_polygon_iter() {
+        let (x, z) = (samples[x], samples[z]);
+        assert!((x - z).abs() <= 1.);
+    }
+
+    let map = Heightmap::from_fn(4, 3, |x, y| x + y);
+    assert_eq!(map.shared_vertex_count(), 12);
+    assert_eq!(map.indexed_polygon_count(), 6);
+    for (x, y, z) in map.shared_vertex_iter() {
+        assert_eq!(z, x + y);
+    }
+}
+
+#[test]
+fn test_triangle_grid() {
+    let grid = TriangleGrid::new(3, 2);
+    assert_eq!(grid.shared_vertex_count(), 12);
+    assert_eq!(grid.indexed_polygon_count(), 12);
+    assert_eq!(grid.clone().count(), 12);
+    assert_ccw(grid.clone());
+
+    // every edge has a length of 1
+    for Triangle{x: a, y: b, z: c} in grid.clone() {
+        for &(p, q) in [(a, b), (b, c), (c, a)].iter() {
+            let l = ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt();
+            assert!((l - 1.).abs() < 1e-5);
+        }
+    }
+
+    assert_eq!(grid.grid_coordinates(0), (0, 0));
+    assert_eq!(grid.grid_coordinates(5), (5, 0));
+    assert_eq!(grid.grid_coordinates(7), (1, 1));
+}
+
+#[test]
+fn test_hex_grid() {
+    assert_eq!(HexGrid::new(1, 1).shared_vertex_count(), 6);
+    assert_eq!(HexGrid::new(2, 1).shared_vertex_count(), 10);
+    assert_eq!(HexGrid::new(2, 2).shared_vertex_count(), 16);
+
+    let grid = HexGrid::new(4, 3);
+    assert_eq!(grid.shared_vertex_count(), 38);
+    assert_eq!(grid.indexed_polygon_count(), 12);
+    assert_eq!(grid.clone().count(), 12);
+    assert_ccw(grid.clone().triangulate());
+    for i in grid.indexed_polygon_iter().vertices() {
+        assert!(i < grid.shared_vertex_count());
+    }
+
+    assert_eq!(grid.grid_coordinates(0), (0, 0));
+    assert_eq!(grid.grid_coordinates(6), (2, 1));
+}
+
+#[test]
+fn test_rounded_box() {
+    // without a radius this is a cube
+    let rounded = RoundedBox::new((1., 1., 1.), 0., 4);
+    let cube = Cube::new();
+    assert_eq!(rounded.shared_vertex_count(), cube.shared_vertex_count());
+    assert_eq!(rounded.indexed_polygon_count(), cube.indexed_polygon_count());
+    for (a, b) in rounded.shared_vertex_iter().zip(cube.shared_vertex_iter()) {
+        assert_eq!(a, b);
+    }
+    for (a, b) in rounded.indexed_polygon_iter().zip(cube.indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+    for (a, b) in rounded.zip(cube) {
+        assert_eq!(a, b);
+    }
+
+    let rounded = RoundedBox::new((2., 1., 0.5), 0.25, 2);
+    assert_eq!(rounded.shared_vertex_count(), 152);
+    assert_eq!(rounded.indexed_polygon_count(), 150);
+    assert_eq!(rounded.clone().count(), 150);
+    assert_outward(rounded.clone().triangulate());
+    assert_closed(rounded.indexed_polygon_iter());
+
+    // every vertex is `radius` away from the inner box
+    for (x, y, z) in rounded.shared_vertex_iter() {
+        let d = |v: f32, h: f32| if v.abs() > h { v.abs() - h } else { 0. };
+        let (dx, dy, dz) = (d(x, 1.75), d(y, 0.75), d(z, 0.25));
+        assert!(((dx*dx + dy*dy + dz*dz).sqrt() - 0.25).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_torus_knot() {
+    let knot = TorusKnot::new(2, 3, 0.1, 64, 8);
+    assert_eq!(knot.shared_vertex_count(), 512);
+    assert_eq!(knot.indexed_polygon_count(), 512);
+    assert_eq!(knot.clone().count(), 512);
+    assert_closed(knot.indexed_polygon_iter());
+    assert!(signed_volume(knot.clone().triangulate()) > 0.);
+    for i in knot.indexed_polygon_iter().vertices() {
+        assert!(i < knot.shared_vertex_count());
+    }
+
+    // the knot stays on its torus, at most the tube radius away
+    for (x, y, z) in knot.shared_vertex_iter() {
+        let r = (x*x + y*y).sqrt() - 1.;
+        assert!(((r*r + z*z).sqrt() - 0.5).abs() < 0.1 + 1e-5);
+    }
+
+    // an open curve is sampled from end to end
+    let tube = Tube::curve(|t| (t, 0., 0.), 5, false, 0.5, 4);
+    assert_eq!(tube.shared_vertex_count(), 20);
+    let verts: Vec<(f32, f32, f32)> = tube.shared_vertex_iter().collect();
+    assert_eq!(verts[0].0, 0.);
+    assert_eq!(verts[16].0, 1.);
+}
+
+#[test]
+fn test_superellipsoid() {
+    // with both exponents at 1 this is a sphere
+    let shape = Superellipsoid::new(1., 1., 8, 6);
+    let sphere = SphereUV::new(8, 6);
+    assert_eq!(shape.shared_vertex_count(), sphere.shared_vertex_count());
+    assert_eq!(shape.indexed_polygon_count(), sphere.indexed_polygon_count());
+    assert_eq!(shape.clone().count(), 48);
+    for (a, b) in shape.shared_vertex_iter().zip(sphere.shared_vertex_iter()) {
+        assert!((a.0 - b.0).abs() < 1e-5 && (a.1 - b.1).abs() < 1e-5 && (a.2 - b.2).abs() < 1e-5);
+    }
+    for (a, b) in shape.indexed_polygon_iter().zip(sphere.indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+
+    for &(e1, e2) in [(0.2, 0.2), (0.2, 1.), (2., 2.), (3., 0.5)].iter() {
+        let shape = Superellipsoid::new(e1, e2, 8, 6).scale(1., 2., 3.);
+        assert_closed(shape.indexed_polygon_iter());
+        assert!(signed_volume(shape.clone().triangulate()) > 0.);
+
+        // every vertex stays in the octant of the matching sphere vertex
+        // and lies on the surface
+        for (a, b) in shape.shared_vertex_iter().zip(sphere.shared_vertex_iter()) {
+            let sign = |v: f32| if v.abs() < 1e-5 { 0. } else { v.signum() };
+            assert_eq!((sign(a.0), sign(a.1), sign(a.2)), (sign(b.0), sign(b.1), sign(b.2)));
+
+            let (x, y, z) = (a.0.abs(), (a.1 / 2.).abs(), (a.2 / 3.).abs());
+            let f = (x.powf(2. / e2) + y.powf(2. / e2)).powf(e2 / e1) + z.powf(2. / e1);
+            assert!((f - 1.).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn test_supertoroid() {
+    // with both exponents at 1 this is a torus
+    let shape = Supertoroid::new(1., 0.25, 1., 1., 8, 6);
+    let torus = Torus::new(1., 0.25, 8, 6);
+    assert_eq!(shape.shared_vertex_count(), torus.shared_vertex_count());
+    assert_eq!(shape.indexed_polygon_count(), torus.indexed_polygon_count());
+    assert_eq!(shape.clone().count(), 48);
+    for (a, b) in shape.shared_vertex_iter().zip(torus.shared_vertex_iter()) {
+        assert!((a.0 - b.0).abs() < 1e-5 && (a.1 - b.1).abs() < 1e-5 && (a.2 - b.2).abs() < 1e-5);
+    }
+    for (a, b) in shape.indexed_polygon_iter().zip(torus.indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+
+    for &(e1, e2) in [(0.2, 0.2), (0.2, 1.), (2., 2.), (3., 0.5)].iter() {
+        let shape = Supertoroid::new(1., 0.25, e1, e2, 8, 6).scale(1., 2., 3.);
+        assert_closed(shape.indexed_polygon_iter());
+        assert!(signed_volume(shape.clone().triangulate()) > 0.);
+
+        // every vertex stays in the octant of the matching torus vertex
+        for (a, b) in shape.shared_vertex_iter().zip(torus.shared_vertex_iter()) {
+            let sign = |v: f32| if v.abs() < 1e-5 { 0. } else { v.signum() };
+            assert_eq!((sign(a.0), sign(a.1), sign(a.2)), (sign(b.0), sign(b.1), sign(b.2)));
+        }
+    }
+}
+
+#[test]
+fn test_cube_sphere() {
+    let triangle_area = |a: (f32, f32, f32), b: (f32, f32, f32), c: (f32, f32, f32)| {
+        let (ux, uy, uz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+        let (vx, vy, vz) = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+        let n = (uy*vz - uz*vy, uz*vx - ux*vz, ux*vy - uy*vx);
+        (n.0*n.0 + n.1*n.1 + n.2*n.2).sqrt() * 0.5
+    };
+    let area = |q: Quad<(f32, f32, f32)>| {
+        triangle_area(q.x, q.y, q.z) + triangle_area(q.x, q.z, q.w)
+    };
+
+    for &equal_area in [false, true].iter() {
+        let sphere = if equal_area { CubeSphere::equal_area(8) } else { CubeSphere::new(8) };
+        let cube = Cube::subdivide(8, 8, 8);
+        assert_eq!(sphere.shared_vertex_count(), cube.shared_vertex_count());
+        assert_eq!(sphere.indexed_polygon_count(), 384);
+        assert_eq!(sphere.clone().count(), 384);
+        for (a, b) in sphere.indexed_polygon_iter().zip(cube.indexed_polygon_iter()) {
+            assert_eq!(a, b);
+        }
+        assert_closed(sphere.indexed_polygon_iter());
+        assert_outward(sphere.clone().triangulate());
+
+        for (x, y, z) in sphere.shared_vertex_iter() {
+            assert!(((x*x + y*y + z*z).sqrt() - 1.).abs() < 1e-5);
+        }
+
+        // each quad lies on the face it reports
+        for (i, Quad{x, y, z, w}) in sphere.clone().enumerate() {
+            let c = [x.0 + y.0 + z.0 + w.0, x.1 + y.1 + z.1 + w.1, x.2 + y.2 + z.2 + w.2];
+            let face = sphere.cube_face(i);
+            let (axis, sign) = (face / 2, if face % 2 == 0 { -1. } else { 1. });
+            assert!(c[axis] * sign > 0.);
+            for n in 0..3 {
+                assert!(c[axis].abs() >= c[n].abs());
+            }
+        }
+
+        let areas: Vec<f32> = sphere.map(|q| area(q)).collect();
+        let min = areas.iter().fold(1., |a, &b| if b < a { b } else { a });
+        let max = areas.iter().fold(0., |a, &b| if b > a { b } else { a });
+        if equal_area {
+            assert!(max / min < 1.1);
+        } else {
+            assert!(max / min > 2.);
+        }
+    }
+}
+
+#[test]
+fn test_sphere_partial() {
+    use std::f32::consts::PI;
+
+    // the full range is the same sphere
+    let sphere = SphereUV::new(8, 6).phi(0., 2. * PI).theta(0., PI);
+    let full = SphereUV::new(8, 6);
+    assert_eq!(sphere.shared_vertex_count(), 42);
+    for (a, b) in sphere.shared_vertex_iter().zip(full.shared_vertex_iter()) {
+        assert_eq!(a, b);
+    }
+    for (a, b) in sphere.indexed_polygon_iter().zip(full.indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+
+    let check = |sphere: SphereUV, vertices: usize, tris: usize| {
+        assert_eq!(sphere.shared_vertex_count(), vertices);
+        assert_eq!(sphere.indexed_polygon_count(), 48);
+        assert_eq!(sphere.count(), 48);
+        assert_eq!(sphere.indexed_polygon_iter().filter(|p| match *p {
+            Polygon::PolyTri(_) => true,
+            _ => false
+        }).count(), tris);
+        assert_outward(sphere.triangulate());
+
+        // the iterator and the shared vertices agree
+        let a = sphere.triangulate().vertices();
+        let b = sphere.indexed_polygon_iter().triangulate().vertices();
+        for (a, i) in a.zip(b) {
+            let b = sphere.shared_vertex(i);
+            assert!((a.0 - b.0).abs() < 1e-5 && (a.1 - b.1).abs() < 1e-5 && (a.2 - b.2).abs() < 1e-5);
+        }
+
+        // no two shared vertices are the same, except the ones on a pole
+        let verts: Vec<(f32, f32, f32)> = sphere.shared_vertex_iter().collect();
+        for (i, a) in verts.iter().enumerate() {
+            for b in verts[i+1..].iter() {
+                let d = (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs();
+                assert!(d > 1e-5);
+            }
+        }
+    };
+
+    // a dome only reaches the top pole
+    let dome = SphereUV::new(8, 6).theta(0., PI * 0.5);
+    check(dome, 6 * 8 + 1, 8);
+    for (_, _, z) in dome.shared_vertex_iter() {
+        assert!(z > -1e-5);
+    }
+
+    // a band reaches neither pole
+    check(SphereUV::new(8, 6).theta(PI * 0.25, PI * 0.5), 7 * 8, 0);
+
+    // the bottom half
+    check(SphereUV::new(8, 6).theta(PI * 0.5, PI * 0.5), 6 * 8 + 1, 8);
+
+    // a wedge gets a seam vertex on every ring
+    let wedge = SphereUV::new(8, 6).phi(PI * 0.25, PI);
+    check(wedge, 5 * 9 + 2, 16);
+    let verts: Vec<(f32, f32, f32)> = wedge.shared_vertex_iter().collect();
+    let (a, b) = (verts[1], verts[9]);
+    assert!((a.0 - a.1).abs() < 1e-5 && a.0 > 0.);
+    assert!((b.0 - b.1).abs() < 1e-5 && b.0 < 0.);
+
+    // a radar sweep of a cap
+    check(SphereUV::new(8, 6).phi(0., PI * 0.5).theta(0.1, 0.5), 7 * 9, 0);
+}
+
+#[test]
+fn test_bezier_patch() {
+    // a flat patch with evenly spaced control points is a plane
+    let mut flat = [(0., 0., 0.); 16];
+    let mut bent = [(0., 0., 0.); 16];
+    for j in 0..4 {
+        for i in 0..4 {
+            let (x, y) = (i as f32 / 3., j as f32 / 3.);
+            flat[4 * j + i] = (x, y, 0.);
+            bent[4 * j + i] = (x + 2., y, if i == 1 || i == 2 { 1. } else { 0. });
+        }
+    }
+
+    let patch = BezierPatch::new(vec![flat, bent], 4, 2);
+    let up = |(x, y, z): (f32, f32, f32)| {
+        x.abs() < 1e-5 && y.abs() < 1e-5 && (z - 1.).abs() < 1e-5
+    };
+    assert_eq!(patch.shared_vertex_count(), 30);
+    assert_eq!(patch.indexed_polygon_count(), 16);
+    assert_eq!(patch.clone().count(), 16);
+    for i in patch.indexed_polygon_iter().vertices() {
+        assert!(i < patch.shared_vertex_count());
+    }
+
+    for i in 0..15 {
+        let (x, y, z) = patch.shared_vertex(i);
+        assert!((x - (i % 5) as f32 / 4.).abs() < 1e-5);
+        assert!((y - (i / 5) as f32 / 2.).abs() < 1e-5);
+        assert_eq!(z, 0.);
+        assert!(up(patch.shared_normal(i)));
+    }
+
+    // the middle of the bent patch is 3/4 of the way up its control points
+    assert!((patch.shared_vertex(15 + 2).2 - 0.75).abs() < 1e-5);
+    assert!(up(patch.shared_normal(15 + 2)));
+    let (x, _, z) = patch.shared_normal(15);
+    assert!(x < 0. && z > 0.);
+    assert_outward(patch.clone().take(8).triangulate().vertex(|(x, y, z)| (x - 0.5, y - 0.5, z + 1.)));
+}
+
+#[test]
+fn test_teapot() {
+    let teapot = Teapot::new(4);
+    assert_eq!(teapot.shared_vertex_count(), 32 * 25);
+    assert_eq!(teapot.indexed_polygon_count(), 32 * 16);
+    assert_eq!(teapot.clone().count(), 32 * 16);
+    for i in teapot.indexed_polygon_iter().vertices() {
+        assert!(i < teapot.shared_vertex_count());
+    }
+
+    // the teapot stands on z = 0, and every normal has a length of 1
+    for (i, (x, y, z)) in teapot.shared_vertex_iter().enumerate() {
+        assert!(z > -1e-5 && z < 3.15 + 1e-5);
+        assert!(x > -3. - 1e-5 && x < 3.525 && y.abs() <= 2. + 1e-5);
+
+        let (nx, ny, nz) = teapot.shared_normal(i);
+        assert!(((nx*nx + ny*ny + nz*nz).sqrt() - 1.).abs() < 1e-5);
+    }
+
+    // the body faces away from the z axis, the lid faces up and
+    // the bottom faces down, both turn sideways at their edges
+    for i in 0..24 * 25 {
+        let (x, y, _) = teapot.shared_vertex(i);
+        let (nx, ny, nz) = teapot.shared_normal(i);
+        let patch = i / 25;
+        if patch >= 4 && patch < 8 {
+            assert!(x*nx + y*ny > 0.);
+        } else if patch >= 16 && patch < 20 {
+            assert!(nz > -1e-5);
+        } else if patch >= 20 {
+            assert!(nz < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn test_marching_cubes() {
+    let sphere = |x: f32, y: f32, z: f32| (x*x + y*y + z*z).sqrt() - 0.8;
+    let surface = MarchingCubes::new(sphere, (-1., -1., -1.), (1., 1., 1.), (16, 16, 16), 0.);
+    assert!(surface.indexed_polygon_count() > 0);
+    assert_eq!(surface.clone().count(), surface.indexed_polygon_count());
+    assert_closed(surface.indexed_polygon_iter());
+    assert_outward(surface.clone());
+    for (x, y, z) in surface.shared_vertex_iter() {
+        assert!(((x*x + y*y + z*z).sqrt() - 0.8).abs() < 0.01);
+    }
+    let volume = signed_volume(surface.clone());
+    assert!((volume - 4. / 3. * std::f32::consts::PI * 0.512).abs() < 0.05);
+
+    // every vertex is welded, no two are the same
+    let mut verts: Vec<(f32, f32, f32)> = surface.shared_vertex_iter().collect();
+    verts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for i in 1..verts.len() {
+        assert!(verts[i - 1] != verts[i]);
+    }
+
+    // metaballs, the iso-level picks the surface
+    let balls = |x: f32, y: f32, z: f32| {
+        let a = 1. / ((x - 0.4).powi(2) + y*y + z*z);
+        let b = 1. / ((x + 0.4).powi(2) + y*y + z*z);
+        -(a + b)
+    };
+    let surface = MarchingCubes::new(balls, (-1.5, -1., -1.), (1.5, 1., 1.), (24, 16, 16), -6.);
+    assert_closed(surface.indexed_polygon_iter());
+    assert!(signed_volume(surface.clone()) > 0.);
+
+    // a surface that leaves the bounds is open
+    let plane = |_: f32, _: f32, z: f32| z;
+    let surface = MarchingCubes::new(plane, (-1., -1., -1.), (1., 1., 1.), (4, 4, 4), 0.1);
+    assert_eq!(surface.shared_vertex_count(), 25);
+    assert_eq!(surface.indexed_polygon_count(), 32);
+    for Triangle{x, y, z} in surface {
+        assert!((x.2 - 0.1).abs() < 1e-5 && (y.2 - 0.1).abs() < 1e-5 && (z.2 - 0.1).abs() < 1e-5);
+    }
+
+    // nothing crosses the iso-level
+    let surface = MarchingCubes::new(sphere, (-1., -1., -1.), (1., 1., 1.), (4, 4, 4), -1.);
+    assert_eq!(surface.indexed_polygon_count(), 0);
+}
+
+#[test]
+fn test_surface_nets() {
+    let sphere = |x: f32, y: f32, z: f32| (x*x + y*y + z*z).sqrt() - 0.8;
+    let surface = SurfaceNets::new(sphere, (-1., -1., -1.), (1., 1., 1.), (16, 16, 16), 0.);
+    assert!(surface.indexed_polygon_count() > 0);
+    assert_eq!(surface.clone().count(), surface.indexed_polygon_count());
+    assert_closed(surface.indexed_polygon_iter());
+    assert_outward(surface.clone().triangulate());
+    for (x, y, z) in surface.shared_vertex_iter() {
+        assert!(((x*x + y*y + z*z).sqrt() - 0.8).abs() < 0.125);
+    }
+    let volume = signed_volume(surface.clone().triangulate());
+    assert!((volume - 4. / 3. * std::f32::consts::PI * 0.512).abs() < 0.1);
+    for i in surface.indexed_polygon_iter().vertices() {
+        assert!(i < surface.shared_vertex_count());
+    }
+
+    // nothing crosses the iso-level
+    let surface = SurfaceNets::new(sphere, (-1., -1., -1.), (1., 1., 1.), (4, 4, 4), -1.);
+    assert_eq!(surface.indexed_polygon_count(), 0);
+}
+
+#[test]
+fn test_dual_contouring() {
+    // a box keeps its sharp corners, which are not on the grid
+    let cube = |x: f32, y: f32, z: f32| {
+        let (qx, qy, qz) = (x.abs() - 0.55, y.abs() - 0.45, z.abs() - 0.35);
+        let (ox, oy, oz) = (qx.max(0.), qy.max(0.), qz.max(0.));
+        (ox*ox + oy*oy + oz*oz).sqrt() + qx.max(qy).max(qz).min(0.)
+    };
+    let surface = DualContouring::new(cube, (-1., -1., -1.), (1., 1., 1.), (10, 10, 10), 0.);
+    assert!(surface.indexed_polygon_count() > 0);
+    assert_eq!(surface.clone().count(), surface.indexed_polygon_count());
+    assert_closed(surface.indexed_polygon_iter());
+    assert_outward(surface.clone().triangulate());
+    assert!((signed_volume(surface.clone().triangulate()) - 8. * 0.55 * 0.45 * 0.35).abs() < 1e-3);
+
+    for (x, y, z) in surface.shared_vertex_iter() {
+        assert!(cube(x, y, z).abs() < 1e-3);
+    }
+    for &sx in [-1., 1.].iter() {
+        for &sy in [-1., 1.].iter() {
+            for &sz in [-1., 1.].iter() {
+                let corner = (0.55 * sx, 0.45 * sy, 0.35 * sz);
+                assert!(surface.shared_vertex_iter().any(|(x, y, z)| {
+                    (x - corner.0).abs() < 1e-3 && (y - corner.1).abs() < 1e-3 && (z - corner.2).abs() < 1e-3
+                }));
+            }
+        }
+    }
+
+    // with the gradient given
+    let sphere = |x: f32, y: f32, z: f32| (x*x + y*y + z*z).sqrt() - 0.8;
+    let gradient = |x: f32, y: f32, z: f32| (x, y, z);
+    let surface = DualContouring::with_gradient(sphere, gradient, (-1., -1., -1.), (1., 1., 1.), (16, 16, 16), 0.);
+    assert_closed(surface.indexed_polygon_iter());
+    assert_outward(surface.clone().triangulate());
+    for (x, y, z) in surface.shared_vertex_iter() {
+        assert!(((x*x + y*y + z*z).sqrt() - 0.8).abs() < 0.02);
+    }
+
+    // crossings without a gradient fall back to the other crossings
+    let flat = |x: f32, y: f32, z: f32| if x > 0. { (0., 0., 0.) } else { (x, y, z) };
+    let surface = DualContouring::with_gradient(sphere, flat, (-1., -1., -1.), (1., 1., 1.), (16, 16, 16), 0.);
+    assert_closed(surface.indexed_polygon_iter());
+    for (x, y, z) in surface.shared_vertex_iter() {
+        assert!(x.is_finite() && y.is_finite() && z.is_finite());
+        assert!(((x*x + y*y + z*z).sqrt() - 0.8).abs() < 0.02);
+    }
+}
+
+#[test]
+fn test_voxels() {
+    let check = |voxels: Voxels<u8>, quads: usize, volume: f32| {
+        assert_eq!(voxels.indexed_polygon_count(), quads);
+        assert_eq!(voxels.shared_vertex_count(), quads * 4);
+        assert_eq!(voxels.clone().count(), quads);
+        for i in voxels.indexed_polygon_iter().vertices() {
+            assert!(i < voxels.shared_vertex_count());
+        }
+
+        // the normal of each face matches its winding
+        for Quad{x, y, z, w} in voxels.clone() {
+            let (a, b, c) = (x.position, y.position, z.position);
+            let (ux, uy, uz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+            let (vx, vy, vz) = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+            let n = (uy*vz - uz*vy, uz*vx - ux*vz, ux*vy - uy*vx);
+            let m = x.normal;
+            assert!(n.0*m.0 + n.1*m.1 + n.2*m.2 > 0.);
+            assert!(x.normal == w.normal && x.material == w.material && x.material != 0);
+        }
+
+        let tris = voxels.vertex(|v: VoxelVertex<u8>| v.position).triangulate();
+        assert!((signed_volume(tris) - volume).abs() < 1e-5);
+    };
+
+    check(Voxels::culled((1, 1, 1), vec![1], |b| b == 0), 6, 1.);
+    check(Voxels::greedy((1, 1, 1), vec![1], |b| b == 0), 6, 1.);
+    check(Voxels::culled((1, 1, 1), vec![0], |b| b == 0), 0, 0.);
+
+    // a solid slab
+    check(Voxels::culled((3, 2, 1), vec![1; 6], |b| b == 0), 22, 6.);
+    check(Voxels::greedy((3, 2, 1), vec![1; 6], |b| b == 0), 6, 6.);
+
+    // faces of different materials are not merged
+    check(Voxels::culled((2, 1, 1), vec![1, 2], |b| b == 0), 10, 2.);
+    check(Voxels::greedy((2, 1, 1), vec![1, 2], |b| b == 0), 10, 2.);
+
+    // a hollow in the middle of a cube has faces on the inside
+    let mut blocks = vec![1; 27];
+    blocks[13] = 0;
+    check(Voxels::culled((3, 3, 3), blocks.clone(), |b| b == 0), 60, 26.);
+    check(Voxels::greedy((3, 3, 3), blocks.clone(), |b| b == 0), 12, 26.);
+
+    // the volume is laid out like a [x][y][z] array
+    let mut blocks = vec![0; 24];
+    blocks[(1 * 3 + 2) * 4 + 3] = 7;
+    let voxels = Voxels::greedy((2, 3, 4), blocks, |b| b == 0);
+    assert_eq!(voxels.indexed_polygon_count(), 6);
+    for i in 0..voxels.shared_vertex_count() {
+        let VoxelVertex{position: (x, y, z), material, ..} = voxels.shared_vertex(i);
+        assert_eq!(material, 7);
+        assert!(x >= 1. && x <= 2. && y >= 2. && y <= 3. && z >= 3. && z <= 4.);
+    }
+}
+
+#[test]
+fn test_normals() {
+    // the flat cube has four vertices for each face
+    let cube = Cube::new().normals();
+    assert_eq!(cube.shared_vertex_count(), 24);
+    assert_eq!(cube.indexed_polygon_count(), 6);
+    assert_eq!(cube.clone().count(), 6);
+    for (a, b) in cube.indexed_polygon_iter().zip(Cube::new().indexed_polygon_iter()) {
+        let a = a.map_vertex(|i| cube.shared_vertex(i).pos);
+        let b = b.map_vertex(|i| Cube::new().shared_vertex(i));
+        assert_eq!(a, b);
+    }
+    for Quad{x, y, z, w} in cube.clone() {
+        assert!(x.normal == y.normal && x.normal == z.normal && x.normal == w.normal);
+        let n = x.normal;
+        let c = (x.pos.0 + z.pos.0, x.pos.1 + z.pos.1, x.pos.2 + z.pos.2);
+        assert_eq!((c.0 * 0.5, c.1 * 0.5, c.2 * 0.5), n);
+    }
+    let mut corners: Vec<(f32, f32, f32)> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    corners.dedup();
+    assert_eq!(corners.len(), 8);
+
+    // the plane faces up
+    let plane = Plane::subdivide(2, 3).normals();
+    assert_eq!(plane.shared_vertex_count(), 12);
+    assert_eq!(plane.indexed_polygon_count(), 6);
+    for (i, Vertex{pos, normal}) in plane.shared_vertex_iter().enumerate() {
+        let (x, y) = Plane::subdivide(2, 3).shared_vertex(i);
+        assert_eq!(pos, (x, y, 0.));
+        assert_eq!(normal, (0., 0., 1.));
+    }
+    for Triangle{x, y, z} in plane.clone().triangulate() {
+        let (ux, uy) = (y.pos.0 - x.pos.0, y.pos.1 - x.pos.1);
+        let (vx, vy) = (z.pos.0 - x.pos.0, z.pos.1 - x.pos.1);
+        assert!(ux*vy - uy*vx > 0.);
+    }
+
+    // the sphere keeps its shared vertices, the normals point out
+    let sphere = SphereUV::new(8, 6).normals();
+    assert_eq!(sphere.shared_vertex_count(), SphereUV::new(8, 6).shared_vertex_count());
+    assert_eq!(sphere.clone().count(), 48);
+    for (a, b) in sphere.indexed_polygon_iter().zip(SphereUV::new(8, 6).indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+    for Vertex{pos, normal} in sphere.shared_vertex_iter() {
+        let d = (pos.0 - normal.0).abs() + (pos.1 - normal.1).abs() + (pos.2 - normal.2).abs();
+        assert!(d < 1e-5);
+    }
+
+    // any other generator can be given flat or smooth normals
+    let cone = Cone::new(8);
+    let flat = Normals::flat(&cone);
+    assert_eq!(flat.indexed_polygon_count(), 16);
+    assert_eq!(flat.shared_vertex_count(), 8 * 3 + 8 * 3);
+    assert_outward(flat.vertex(|v: Vertex| v.pos).triangulate());
+
+    let smooth = Normals::smooth(&IcoSphere::subdivide(2));
+    assert_eq!(smooth.shared_vertex_count(), IcoSphere::subdivide(2).shared_vertex_count());
+    for Vertex{pos, normal} in smooth.shared_vertex_iter() {
+        let l = (normal.0*normal.0 + normal.1*normal.1 + normal.2*normal.2).sqrt();
+        assert!((l - 1.).abs() < 1e-5);
+        assert!(pos.0*normal.0 + pos.1*normal.1 + pos.2*normal.2 > 0.99);
+    }
+}
+
+#[test]
+fn test_textured() {
+    // each face of the cube is covered by the whole texture
+    let cube = Cube::new().textured();
+    assert_eq!(cube.shared_vertex_count(), 24);
+    assert_eq!(cube.indexed_polygon_count(), 6);
+    for Quad{x, y, z, w} in cube.clone() {
+        assert_eq!((x.uv, y.uv, z.uv, w.uv), ((0., 0.), (1., 0.), (1., 1.), (0., 1.)));
+        assert!(x.normal == y.normal && x.normal == z.normal && x.normal == w.normal);
+        let c = (x.pos.0 + z.pos.0, x.pos.1 + z.pos.1, x.pos.2 + z.pos.2);
+        assert_eq!((c.0 * 0.5, c.1 * 0.5, c.2 * 0.5), x.normal);
+    }
+    let positions = |q: Quad<TexturedVertex>| q.map_vertex(|v| v.pos);
+    let cube = Cube::subdivide(2, 3, 4);
+    assert_eq!(cube.textured().map(&positions).collect::<Vec<_>>(), cube.clone().collect::<Vec<_>>());
+    assert_eq!(cube.textured().shared_vertex_count(), 2 * (4*5 + 3*5 + 3*4));
+    assert_outward(cube.textured().vertex(|v: TexturedVertex| v.pos).triangulate());
+
+    // the texture is not mirrored when seen from outside
+    let uv_area = |Triangle{x, y, z}: Triangle<TexturedVertex>| {
+        let (ux, uy) = (y.uv.0 - x.uv.0, y.uv.1 - x.uv.1);
+        let (vx, vy) = (z.uv.0 - x.uv.0, z.uv.1 - x.uv.1);
+        ux*vy - uy*vx
+    };
+    for t in cube.textured().triangulate() {
+        assert!(uv_area(t) > 0.);
+    }
+
+    // the plane keeps its shared vertices
+    let plane = Plane::subdivide(2, 4).textured();
+    assert_eq!(plane.shared_vertex_count(), 15);
+    for (a, b) in plane.indexed_polygon_iter().zip(Plane::subdivide(2, 4).indexed_polygon_iter()) {
+        assert_eq!(a, b);
+    }
+    for TexturedVertex{pos, normal, uv} in plane.shared_vertex_iter() {
+        assert_eq!(normal, (0., 0., 1.));
+        assert_eq!(uv, ((pos.0 + 1.) * 0.5, (pos.1 + 1.) * 0.5));
+    }
+    for t in plane.clone().triangulate() {
+        assert!(uv_area(t) > 0.);
+    }
+
+    // the sphere has a seam column and a vertex for each triangle at the poles
+    let sphere = SphereUV::new(8, 6).textured();
+    assert_eq!(sphere.shared_vertex_count(), 8 + 5 * 9 + 8);
+    assert_eq!(sphere.indexed_polygon_count(), 48);
+    let polygons: Vec<Polygon<TexturedVertex>> = sphere.clone().collect();
+    let expected: Vec<Polygon<(f32, f32, f32)>> = SphereUV::new(8, 6).collect();
+    for (a, b) in polygons.iter().zip(expected.iter()) {
+        let a: Vec<(f32, f32, f32)> = vec![*a].into_iter().vertex(|v: TexturedVertex| v.pos).vertices().collect();
+        let b: Vec<(f32, f32, f32)> = vec![*b].into_iter().vertices().collect();
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs() < 1e-5);
+        }
+    }
+    for t in sphere.clone().triangulate() {
+        assert!(uv_area(t) > 0.);
+        for v in [t.x, t.y, t.z].iter() {
+            assert!(v.uv.0 >= 0. && v.uv.0 <= 1. && v.uv.1 >= 0. && v.uv.1 <= 1.);
+            assert_eq!(v.pos, v.normal);
+        }
+        // no triangle wraps around the texture
+        assert!((t.x.uv.0 - t.y.uv.0).abs() < 0.2 && (t.x.uv.0 - t.z.uv.0).abs() < 0.2);
+    }
+    for (u, p) in sphere.clone().take(8).enumerate() {
+        match p {
+            Polygon::PolyTri(t) => {
+                assert!((t.x.uv.0 - (u as f32 + 0.5) / 8.).abs() < 1e-6 && t.x.uv.1 == 1.);
+                assert_eq!(t.x.pos, (0., 0., 1.));
+            }
+            Polygon::PolyQuad(_) => panic!("expected a triangle at the pole")
+        }
+    }
+
+    // a part of the sphere gets the matching part of the texture
+    let dome = SphereUV::new(8, 3).theta(0., std::f32::consts::PI * 0.5).textured();
+    for TexturedVertex{uv, ..} in dome.shared_vertex_iter() {
+        assert!(uv.1 >= 0.5 - 1e-6);
+    }
+}